@@ -0,0 +1,104 @@
+// Compares the trace-based subgroup checks of `Pairing` with the ones built
+// on the Frobenius endomorphism, and the [r]Q check on a twist with the one
+// built on ψ. Run with `cargo bench`.
+
+use std::time::{Duration, Instant};
+
+use pairings_from_scratch::{
+    curves::{Bn254, Bn254Twist, MoonMath, MoonMathTwist, TinyJJ},
+    elliptic_curve::{AffinePoint, EllipticCurve},
+    pairing::Pairing,
+    polynomial::Polynomial,
+    twist::Twist,
+};
+
+/// The average time of a check over a number of runs.
+fn time<F: Fn() -> bool>(runs: u32, check: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..runs {
+        assert!(check());
+    }
+    start.elapsed() / runs
+}
+
+fn report(name: &str, runs: u32, old: impl Fn() -> bool, new: impl Fn() -> bool) {
+    let (old, new) = (time(runs, old), time(runs, new));
+    println!(
+        "{:<28} {:>12.3?} {:>12.3?} {:>8.2}x",
+        name,
+        old,
+        new,
+        old.as_secs_f64() / new.as_secs_f64()
+    );
+}
+
+fn main() {
+    println!(
+        "{:<28} {:>12} {:>12} {:>9}",
+        "check", "old", "new", "speedup"
+    );
+
+    let p = AffinePoint::<TinyJJ>::try_new_g1(
+        Polynomial::from(vec![8]).into(),
+        Polynomial::from(vec![8]).into(),
+    )
+    .unwrap();
+    let q = AffinePoint::<TinyJJ>::try_new_g2(
+        Polynomial::from(vec![7, 0, 4]).into(),
+        Polynomial::from(vec![0, 10, 0, 5]).into(),
+    )
+    .unwrap();
+    report(
+        "TinyJJ G1",
+        100,
+        || TinyJJ::is_valid_g1(&p),
+        || TinyJJ::is_valid_g1_frobenius(&p),
+    );
+    report(
+        "TinyJJ G2",
+        100,
+        || TinyJJ::is_valid_g2(&q),
+        || TinyJJ::is_valid_g2_frobenius(&q),
+    );
+
+    let p = AffinePoint::<MoonMath>::try_new_g1(
+        Polynomial::from(vec![27]).into(),
+        Polynomial::from(vec![34]).into(),
+    )
+    .unwrap();
+    let q = MoonMath::generator();
+    report(
+        "MoonMath G1",
+        100,
+        || MoonMath::is_valid_g1(&p),
+        || MoonMath::is_valid_g1_frobenius(&p),
+    );
+    report(
+        "MoonMath G2",
+        100,
+        || MoonMath::is_valid_g2(&q),
+        || MoonMath::is_valid_g2_frobenius(&q),
+    );
+    let q = MoonMathTwist::generator();
+    report(
+        "MoonMath G2 on the twist",
+        100,
+        || MoonMath::is_valid_twisted_g2(&q),
+        || MoonMath::is_valid_twisted_g2_psi(&q),
+    );
+
+    let p = Bn254::generator();
+    report(
+        "BN254 G1",
+        3,
+        || Bn254::is_valid_g1(&p),
+        || Bn254::is_valid_g1_frobenius(&p),
+    );
+    let q = Bn254Twist::generator();
+    report(
+        "BN254 G2 on the twist",
+        3,
+        || Bn254::is_valid_twisted_g2(&q),
+        || Bn254::is_valid_twisted_g2_psi(&q),
+    );
+}
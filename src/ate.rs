@@ -0,0 +1,320 @@
+use num_bigint::BigInt;
+use num_traits::{One, Signed};
+
+use crate::{
+    elliptic_curve::{AffinePoint, EllipticCurve},
+    field_element::FieldElement,
+    pairing::{dist_relationship, Pairing},
+    twist::Twist,
+};
+
+// Ate pairings.
+//
+// The Tate pairing runs Miller's loop over the bits of r with P in G1. The
+// ate pairing swaps the roles of the points and runs the loop with Q in G2
+// over T = t - 1, t being the trace of Frobenius, which is much shorter
+// than r on pairing-friendly curves:
+//
+//     a(Q, P) = f_{T,Q}(P)^((q^k - 1) / r)
+//
+// It is a power of the Tate pairing t(Q, P) with Q first: a^(k q^(k-1)) =
+// t^L with L = (T^k - 1) / r. The optimal ate pairing shortens the loop to
+// about r^(1/φ(k)) from a multiple of r written as a short combination of
+// powers of q. For BLS curves this is u itself, and for BN curves it is
+//
+//     6u + 2 + q - q^2 + q^3 = 0 (mod r)
+//
+// so the loop over 6u + 2 is followed by two lines through π(Q) and
+// -π^2(Q), the Frobenius images of Q. The point is kept on the twist, where
+// π is the ψ map and the arithmetic is over a smaller field; it is only
+// untwisted to evaluate the lines at P.
+
+/// The families of curves with a known optimal ate loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveFamily {
+    /// Barreto-Naehrig curves, with k = 12 and q, r of degree 4 in u.
+    Bn,
+    /// Barreto-Lynn-Scott curves such as BLS12 and BLS6, with t = u + 1.
+    Bls,
+}
+
+pub trait AtePairing: Twist {
+    /// The family the curve belongs to.
+    fn family() -> CurveFamily;
+
+    /// The parameter u the polynomials of the family are evaluated at.
+    fn u() -> BigInt;
+
+    /// The trace of Frobenius over the field of definition: 6u^2 + 1 for BN
+    /// and u + 1 for BLS curves.
+    fn trace() -> BigInt {
+        let u = Self::u();
+        match Self::family() {
+            CurveFamily::Bn => &u * &u * 6 + 1,
+            CurveFamily::Bls => u + 1,
+        }
+    }
+
+    /// The loop count T = t - 1 of the ate pairing.
+    fn ate_loop_count() -> BigInt {
+        Self::trace() - 1
+    }
+
+    /// The loop count of the optimal ate pairing: 6u + 2 for BN and u for
+    /// BLS curves.
+    fn optimal_ate_loop_count() -> BigInt {
+        let u = Self::u();
+        match Self::family() {
+            CurveFamily::Bn => u * 6 + 2,
+            CurveFamily::Bls => u,
+        }
+    }
+
+    /// Evaluates at `p` the Miller function f_{n,Q} of the twisted point `q`,
+    /// and returns it with [n]Q. Vertical lines are left out, as for
+    /// `miller_loop`, since the final exponentiation wipes them out. For a
+    /// negative n, f_{n,Q} = 1 / f_{-n,Q} up to a vertical line.
+    fn ate_miller_loop(
+        q: &AffinePoint<Self::TwistedCurve>,
+        p: &AffinePoint<Self>,
+        n: &BigInt,
+    ) -> (
+        FieldElement<Self::BaseField>,
+        AffinePoint<Self::TwistedCurve>,
+    ) {
+        let mut point = q.clone();
+        let mut f = FieldElement::<Self::BaseField>::one();
+        let untwisted = Self::untwist(q);
+
+        let bits = n.abs().to_str_radix(2);
+        for bit in bits.chars().skip(1) {
+            let t = Self::untwist(&point);
+            f = f.clone() * f * dist_relationship(&t, &t, p);
+            point = point.double();
+
+            if bit == '1' {
+                f = f * dist_relationship(&Self::untwist(&point), &untwisted, p);
+                point = point + q.clone();
+            }
+        }
+
+        if n.is_negative() {
+            (f.inverse(), -point)
+        } else {
+            (f, point)
+        }
+    }
+
+    /// The ate pairing of a G1 point and a G2 point given on the twist.
+    fn ate_pairing(
+        p: &AffinePoint<Self>,
+        q: &AffinePoint<Self::TwistedCurve>,
+    ) -> FieldElement<Self::BaseField> {
+        assert!(Self::is_valid_g1(p), "p is not a G1 point");
+        assert!(Self::is_valid_twisted_g2(q), "q is not a G2 point");
+
+        let (f, _) = Self::ate_miller_loop(q, p, &Self::ate_loop_count());
+        Self::final_exponentiation(f)
+    }
+
+    /// The Miller loop of the optimal ate pairing, with the two Frobenius
+    /// lines at the end for BN curves. Products of these values share a
+    /// single final exponentiation.
+    fn optimal_ate_miller_loop(
+        p: &AffinePoint<Self>,
+        q: &AffinePoint<Self::TwistedCurve>,
+    ) -> FieldElement<Self::BaseField> {
+        let (mut f, point) = Self::ate_miller_loop(q, p, &Self::optimal_ate_loop_count());
+
+        if Self::family() == CurveFamily::Bn {
+            let q1 = Self::psi(q);
+            let q2 = -Self::psi(&q1);
+            f = f * dist_relationship(&Self::untwist(&point), &Self::untwist(&q1), p);
+            let point = point + q1;
+            f = f * dist_relationship(&Self::untwist(&point), &Self::untwist(&q2), p);
+        }
+        f
+    }
+
+    /// The optimal ate pairing of a G1 point and a G2 point given on the
+    /// twist.
+    fn optimal_ate_pairing(
+        p: &AffinePoint<Self>,
+        q: &AffinePoint<Self::TwistedCurve>,
+    ) -> FieldElement<Self::BaseField> {
+        assert!(Self::is_valid_g1(p), "p is not a G1 point");
+        assert!(Self::is_valid_twisted_g2(q), "q is not a G2 point");
+
+        let f = Self::optimal_ate_miller_loop(p, q);
+        Self::final_exponentiation(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_integer::Integer;
+    use num_traits::Pow;
+
+    use crate::{
+        curves::{MoonMath, MoonMathTwist, TinyBn, TinyBnTwist},
+        finite_field::NonExtendedField,
+        point_counting,
+        polynomial::Polynomial,
+    };
+
+    use super::*;
+
+    fn moonmath_points() -> (AffinePoint<MoonMath>, AffinePoint<MoonMathTwist>) {
+        let p = AffinePoint::<MoonMath>::try_new_g1(
+            Polynomial::from(vec![27]).into(),
+            Polynomial::from(vec![34]).into(),
+        )
+        .unwrap();
+        // the twist has order 4 * 13
+        let q = MoonMathTwist::generator() * 4;
+        (p, q)
+    }
+
+    fn tinybn_points() -> (AffinePoint<TinyBn>, AffinePoint<TinyBnTwist>) {
+        (TinyBn::generator(), TinyBnTwist::generator())
+    }
+
+    fn r<E: AtePairing>() -> BigInt {
+        E::ScalarField::to_bigint(&E::r())
+    }
+
+    /// Checks that the loop counts are multiples of r written in base q.
+    fn assert_loop_counts<E: AtePairing>() {
+        let (q, _) = point_counting::field_of_definition::<E>();
+        let r = r::<E>();
+        assert_eq!((E::ate_loop_count() - &q) % &r, BigInt::from(0));
+
+        let lambda = match E::family() {
+            CurveFamily::Bn => {
+                E::optimal_ate_loop_count() + &q - q.clone().pow(2u32) + q.clone().pow(3u32)
+            }
+            CurveFamily::Bls => E::optimal_ate_loop_count() - &q,
+        };
+        assert_eq!(lambda % &r, BigInt::from(0));
+    }
+
+    /// Checks a(Q, P)^(k q^(k-1)) = t(Q, P)^L with L = (T^k - 1) / r, the
+    /// Tate pairing being taken with Q first.
+    fn assert_ate_tate<E: AtePairing>(p: &AffinePoint<E>, q: &AffinePoint<E::TwistedCurve>) {
+        let (size, _) = point_counting::field_of_definition::<E>();
+        let k = E::embedding_degree();
+        let r = r::<E>();
+        let t = E::ate_loop_count();
+        let l = (t.pow(k) - 1) / &r;
+        let c = size.pow(k - 1) * k;
+
+        let f = E::miller_function(&E::untwist(q), p).unwrap();
+        let tate = E::final_exponentiation(f);
+        let ate = E::ate_pairing(p, q);
+        assert_ne!(ate, FieldElement::one());
+        assert_eq!(ate.pow_bigint(&(c % &r)), tate.pow_bigint(&(l % &r)));
+    }
+
+    /// The coefficients c_i of λ = Σ c_i q^i, the multiple of r the optimal
+    /// ate loop is built from.
+    fn lambda_coefficients<E: AtePairing>() -> Vec<BigInt> {
+        let one = BigInt::one();
+        match E::family() {
+            CurveFamily::Bn => vec![E::optimal_ate_loop_count(), one.clone(), -one.clone(), one],
+            CurveFamily::Bls => vec![E::optimal_ate_loop_count(), -one],
+        }
+    }
+
+    /// Checks that the optimal ate pairing is bilinear, non-degenerate and a
+    /// known power of the Tate pairing t(Q, P) with Q first. Writing
+    /// λ = Σ c_i q^i = m r, f_{λ,Q} = f_{r,Q}^m splits into the optimal ate
+    /// Miller function times Π f_{q^i,Q}^{c_i}, and f_{q^i,Q} = f_{q,Q}^(i q^(i-1))
+    /// with f_{q,Q}^(k q^(k-1)) giving t^N, N = (q^k - 1) / r, after the final
+    /// exponentiation. Hence
+    ///
+    ///     e^(k q^(k-1)) = t^(m k q^(k-1) - N Σ i c_i q^(i-1)).
+    fn assert_optimal_ate<E: AtePairing>(p: &AffinePoint<E>, q: &AffinePoint<E::TwistedCurve>) {
+        let r = r::<E>();
+        let e = E::optimal_ate_pairing(p, q);
+        assert_ne!(e, FieldElement::one());
+        assert_eq!(e.pow_bigint(&r), FieldElement::one());
+
+        let two = BigInt::from(2);
+        let e2 = e.pow_bigint(&two);
+        assert_eq!(E::optimal_ate_pairing(&p.mul_double_and_add(&two), q), e2);
+        assert_eq!(E::optimal_ate_pairing(p, &q.mul_double_and_add(&two)), e2);
+
+        let (size, _) = point_counting::field_of_definition::<E>();
+        let k = E::embedding_degree();
+        let coefficients = lambda_coefficients::<E>();
+        let lambda: BigInt = coefficients
+            .iter()
+            .enumerate()
+            .map(|(i, c)| c * size.clone().pow(i))
+            .sum();
+        assert_eq!(&lambda % &r, BigInt::from(0));
+        let m = lambda / &r;
+        let n = (size.clone().pow(k) - 1) / &r;
+        let derivative: BigInt = coefficients
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| c * i * size.clone().pow(i - 1))
+            .sum();
+        let c: BigInt = size.pow(k - 1) * k;
+        let exponent: BigInt = &m * &c - n * derivative;
+        let exponent = exponent.mod_floor(&r);
+        assert_ne!(exponent, BigInt::from(0));
+
+        let tate = |p: &AffinePoint<E>, q: &AffinePoint<E::TwistedCurve>| {
+            E::final_exponentiation(E::miller_function(&E::untwist(q), p).unwrap())
+        };
+        let c = c % &r;
+        assert_eq!(e.pow_bigint(&c), tate(p, q).pow_bigint(&exponent));
+        let p3 = p.mul_double_and_add(&BigInt::from(3));
+        let q5 = q.mul_double_and_add(&BigInt::from(5));
+        assert_eq!(
+            E::optimal_ate_pairing(&p3, &q5).pow_bigint(&c),
+            tate(&p3, &q5).pow_bigint(&exponent)
+        );
+    }
+
+    #[test]
+    fn test_ate_loop_counts() {
+        assert_eq!(MoonMath::trace(), BigInt::from(5));
+        assert_eq!(MoonMath::optimal_ate_loop_count(), BigInt::from(4));
+        assert_loop_counts::<MoonMath>();
+
+        assert_eq!(TinyBn::trace(), BigInt::from(7));
+        assert_eq!(TinyBn::ate_loop_count(), BigInt::from(6));
+        assert_eq!(TinyBn::optimal_ate_loop_count(), BigInt::from(8));
+        assert_loop_counts::<TinyBn>();
+    }
+
+    #[test]
+    fn test_ate_pairing() {
+        let (p, q) = moonmath_points();
+        assert_ate_tate(&p, &q);
+        let (p, q) = tinybn_points();
+        assert_ate_tate(&p, &q);
+    }
+
+    #[test]
+    fn test_optimal_ate_pairing() {
+        let (p, q) = moonmath_points();
+        assert_optimal_ate(&p, &q);
+        let (p, q) = tinybn_points();
+        assert_optimal_ate(&p, &q);
+    }
+
+    #[test]
+    fn test_ate_negative_loop_count() {
+        // f_{-n,Q} = 1 / f_{n,Q} up to a vertical line, and [-n]Q = -[n]Q
+        let (p, q) = tinybn_points();
+        let n = BigInt::from(8);
+        let (f, point) = TinyBn::ate_miller_loop(&q, &p, &n);
+        let (f_neg, point_neg) = TinyBn::ate_miller_loop(&q, &p, &-n);
+        assert_eq!(point_neg, -point);
+        assert_eq!(TinyBn::final_exponentiation(f * f_neg), FieldElement::one());
+    }
+}
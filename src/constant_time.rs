@@ -0,0 +1,385 @@
+use std::{
+    hint::black_box,
+    ops::{BitAnd, BitOr, Not},
+};
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+
+use crate::{
+    elliptic_curve::{AffinePoint, EllipticCurve},
+    field_element::FieldElement,
+    finite_field::{FiniteField, NonExtendedField},
+    projective::ProjectivePoint,
+};
+
+// An opt-in constant-time path for secret-dependent operations.
+//
+// The rest of the crate branches freely on the values it handles: `to_bits`
+// strips leading zeros, double-and-add only adds on set bits and the xgcd
+// inversion loops a data-dependent number of times. The functions below avoid
+// those branches: secret bits are turned into arithmetic masks, exponents and
+// scalars are always processed with the same number of bits, and points are
+// added with complete formulas that have no special cases.
+//
+// This is best effort only. Field arithmetic is built on top of primitive
+// integers, BigInt and Polynomial, none of which are constant-time themselves,
+// so this removes the most obvious timing leaks and nothing more.
+
+/// A secret boolean, stored as 0 or 1, that is combined with masks
+/// instead of being branched on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Choice(u8);
+
+impl Choice {
+    pub fn unwrap_u8(&self) -> u8 {
+        self.0
+    }
+}
+
+impl From<u8> for Choice {
+    /// Only the lowest bit of the input is kept.
+    fn from(bit: u8) -> Self {
+        Choice(black_box(bit & 1))
+    }
+}
+
+impl From<Choice> for bool {
+    fn from(choice: Choice) -> bool {
+        choice.0 == 1
+    }
+}
+
+impl Not for Choice {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Choice::from(self.0 ^ 1)
+    }
+}
+
+impl BitAnd for Choice {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Choice::from(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for Choice {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Choice::from(self.0 | rhs.0)
+    }
+}
+
+/// Types whose values can be selected by a secret `Choice` without branching.
+pub trait ConditionallySelectable: Clone {
+    /// Returns `a` when `choice` is 0 and `b` when it is 1.
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self;
+
+    /// Replaces `self` with `other` when `choice` is 1.
+    fn conditional_assign(&mut self, other: &Self, choice: Choice) {
+        *self = Self::conditional_select(self, other, choice);
+    }
+
+    /// Swaps `a` and `b` when `choice` is 1.
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        let t = a.clone();
+        a.conditional_assign(b, choice);
+        b.conditional_assign(&t, choice);
+    }
+}
+
+/// Equality that inspects every coefficient instead of returning early.
+pub trait ConstantTimeEq {
+    fn ct_eq(&self, other: &Self) -> Choice;
+}
+
+impl<M: FiniteField> ConditionallySelectable for FieldElement<M> {
+    /// Computes a + (b - a) * c, where c is the choice as a field element,
+    /// so the same operations are performed for both values of the choice.
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let c = FieldElement::new(M::from_coefficients(&[BigInt::from(choice.0)]));
+        a.clone() + (b.clone() - a.clone()) * c
+    }
+}
+
+impl<M: FiniteField> ConstantTimeEq for FieldElement<M> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let lhs = M::to_coefficients(self.value());
+        let rhs = M::to_coefficients(other.value());
+        // the byte length of the largest coefficient, so that all
+        // coefficients are compared with the same number of bytes
+        let len = M::characteristic().bits().div_ceil(8) as usize;
+
+        let mut diff = 0u8;
+        for (a, b) in lhs.iter().zip(rhs.iter()) {
+            let (a, b) = (to_padded_bytes(a, len), to_padded_bytes(b, len));
+            for (x, y) in a.iter().zip(b.iter()) {
+                diff |= x ^ y;
+            }
+        }
+        // diff is 0 exactly when all bytes are equal
+        let diff = black_box(diff) as u16;
+        Choice::from((((diff | diff.wrapping_neg()) >> 8) as u8 & 1) ^ 1)
+    }
+}
+
+/// Big-endian bytes of a non-negative integer, left-padded to `len` bytes.
+fn to_padded_bytes(n: &BigInt, len: usize) -> Vec<u8> {
+    let (_, bytes) = n.to_bytes_be();
+    let mut padded = vec![0; len.saturating_sub(bytes.len())];
+    padded.extend(bytes);
+    padded
+}
+
+impl<E: EllipticCurve> ConditionallySelectable for ProjectivePoint<E> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        ProjectivePoint::new(
+            FieldElement::conditional_select(&a.x, &b.x, choice),
+            FieldElement::conditional_select(&a.y, &b.y, choice),
+            FieldElement::conditional_select(&a.z, &b.z, choice),
+        )
+    }
+}
+
+/// The bits of a non-negative integer, most significant first,
+/// left-padded with zeros to exactly `len` bits.
+fn fixed_bits(n: &BigInt, len: u64) -> Vec<Choice> {
+    (0..len)
+        .rev()
+        .map(|i| Choice::from(n.bit(i) as u8))
+        .collect()
+}
+
+impl<M: FiniteField> FieldElement<M> {
+    /// Exponentiation with a Montgomery ladder over exactly `bits` bits of
+    /// the exponent: every step performs one multiplication and one squaring,
+    /// whatever the value of the bit.
+    pub fn pow_ct(&self, exp: &BigInt, bits: u64) -> Self {
+        assert!(
+            exp.sign() != num_bigint::Sign::Minus && exp.bits() <= bits,
+            "exponent must be non-negative and fit in the given number of bits"
+        );
+        let mut r0 = Self::one();
+        let mut r1 = self.clone();
+        for bit in fixed_bits(exp, bits) {
+            Self::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = r0.clone() * r1;
+            r0 = r0.clone() * r0;
+            Self::conditional_swap(&mut r0, &mut r1, bit);
+        }
+        r0
+    }
+
+    /// Inversion by Fermat's little theorem, a^-1 = a^(|F| - 2),
+    /// with a public exponent. The inverse of zero is zero.
+    pub fn inverse_ct(&self) -> Self {
+        let exp: BigInt = M::size() - 2;
+        let bits = exp.bits();
+        self.pow_ct(&exp, bits)
+    }
+}
+
+impl<E: EllipticCurve> ProjectivePoint<E> {
+    /// Complete addition (Renes, Costello, Batina 2015, algorithms 1 and 7):
+    /// the same formula handles doubling and the point at infinity, with the
+    /// single exception of P - Q being a point of order 2.
+    pub fn add_complete(&self, other: &Self) -> Self {
+        let (x1, y1, z1) = (self.x.clone(), self.y.clone(), self.z.clone());
+        let (x2, y2, z2) = (other.x.clone(), other.y.clone(), other.z.clone());
+        let b3 = E::b() + E::b() + E::b();
+
+        if E::a().is_zero() {
+            let t0 = x1.clone() * x2.clone();
+            let t1 = y1.clone() * y2.clone();
+            let t2 = z1.clone() * z2.clone();
+            let t3 = (x1.clone() + y1.clone()) * (x2.clone() + y2.clone());
+            let t3 = t3 - (t0.clone() + t1.clone());
+            let t4 = (y1 + z1.clone()) * (y2 + z2.clone());
+            let t4 = t4 - (t1.clone() + t2.clone());
+            let y3 = (x1 + z1) * (x2 + z2);
+            let y3 = y3 - (t0.clone() + t2.clone());
+            let t0 = t0.clone() + t0.clone() + t0;
+            let t2 = b3.clone() * t2;
+            let z3 = t1.clone() + t2.clone();
+            let t1 = t1 - t2;
+            let y3 = b3 * y3;
+            let x3 = t3.clone() * t1.clone() - t4.clone() * y3.clone();
+            let y3 = t1 * z3.clone() + y3 * t0.clone();
+            let z3 = z3 * t4 + t0 * t3;
+            return Self::new(x3, y3, z3);
+        }
+
+        let a = E::a();
+        let t0 = x1.clone() * x2.clone();
+        let t1 = y1.clone() * y2.clone();
+        let t2 = z1.clone() * z2.clone();
+        let t3 = (x1.clone() + y1.clone()) * (x2.clone() + y2.clone());
+        let t3 = t3 - (t0.clone() + t1.clone());
+        let t4 = (x1 + z1.clone()) * (x2 + z2.clone());
+        let t4 = t4 - (t0.clone() + t2.clone());
+        let t5 = (y1 + z1) * (y2 + z2);
+        let t5 = t5 - (t1.clone() + t2.clone());
+        let z3 = a.clone() * t4.clone();
+        let x3 = b3.clone() * t2.clone();
+        let z3 = x3 + z3;
+        let x3 = t1.clone() - z3.clone();
+        let z3 = t1.clone() + z3;
+        let y3 = x3.clone() * z3.clone();
+        let t1 = t0.clone() + t0.clone() + t0.clone();
+        let t2 = a.clone() * t2;
+        let t4 = b3 * t4;
+        let t1 = t1 + t2.clone();
+        let t2 = a * (t0 - t2);
+        let t4 = t4 + t2;
+        let y3 = y3 + t1.clone() * t4.clone();
+        let x3 = t3.clone() * x3 - t5.clone() * t4;
+        let z3 = t5 * z3 + t3 * t1;
+        Self::new(x3, y3, z3)
+    }
+
+    /// Conversion to affine coordinates with a Fermat inversion.
+    pub fn to_affine_ct(&self) -> AffinePoint<E> {
+        let z_inv = self.z.inverse_ct();
+        if self.is_inf() {
+            return AffinePoint::Infinity;
+        }
+        AffinePoint::XY(self.x.clone() * z_inv.clone(), self.y.clone() * z_inv)
+    }
+}
+
+impl<E: EllipticCurve> AffinePoint<E> {
+    /// Constant-time scalar multiplication with a Montgomery ladder.
+    ///
+    /// The scalar is reduced modulo the order of the curve and always
+    /// processed with as many bits as the order has. Each step performs one
+    /// complete addition and one complete doubling, and the secret bit only
+    /// decides which registers get swapped. The point must not be of order 2.
+    pub fn mul_ct(&self, scalar: <E::ScalarField as FiniteField>::T) -> Self {
+        let order = E::ScalarField::to_bigint(&E::order());
+        let k = E::ScalarField::to_bigint(&scalar).mod_floor(&order);
+
+        let mut r0 = ProjectivePoint::<E>::infinity();
+        let mut r1 = ProjectivePoint::from(self.clone());
+        // invariant: r1 - r0 = P
+        for bit in fixed_bits(&k, order.bits()) {
+            ProjectivePoint::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = r0.add_complete(&r1);
+            r0 = r0.add_complete(&r0);
+            ProjectivePoint::conditional_swap(&mut r0, &mut r1, bit);
+        }
+        r0.to_affine_ct()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{MoonMath, TinyJJ},
+        fields::{Fe13, Fe13_2, Fe13_4, Fe43_6, FeBn254},
+        polynomial::Polynomial,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_choice() {
+        let yes = Choice::from(1);
+        let no = Choice::from(0);
+        assert_eq!(Choice::from(3), yes);
+        assert_eq!(!yes, no);
+        assert_eq!(yes & no, no);
+        assert_eq!(yes | no, yes);
+        assert!(bool::from(yes));
+    }
+
+    #[test]
+    fn test_conditional_select_and_swap() {
+        let a = Fe13::new(3);
+        let b = Fe13::new(11);
+        assert_eq!(Fe13::conditional_select(&a, &b, Choice::from(0)), a);
+        assert_eq!(Fe13::conditional_select(&a, &b, Choice::from(1)), b);
+
+        let mut x: Fe13_2 = Polynomial::from(vec![7, 3]).into();
+        let mut y: Fe13_2 = Polynomial::from(vec![0, 5]).into();
+        let (x0, y0) = (x.clone(), y.clone());
+        Fe13_2::conditional_swap(&mut x, &mut y, Choice::from(0));
+        assert_eq!((x.clone(), y.clone()), (x0.clone(), y0.clone()));
+        Fe13_2::conditional_swap(&mut x, &mut y, Choice::from(1));
+        assert_eq!((x, y), (y0, x0));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert_eq!(Fe13::new(5).ct_eq(&Fe13::new(5)), Choice::from(1));
+        assert_eq!(Fe13::new(5).ct_eq(&Fe13::new(6)), Choice::from(0));
+
+        let x: Fe13_4 = Polynomial::from(vec![1, 2, 3, 4]).into();
+        let y: Fe13_4 = Polynomial::from(vec![1, 2, 3, 5]).into();
+        assert_eq!(x.ct_eq(&x), Choice::from(1));
+        assert_eq!(x.ct_eq(&y), Choice::from(0));
+
+        let big = FeBn254::new(BigInt::from(1) << 200);
+        assert_eq!(big.ct_eq(&big), Choice::from(1));
+        assert_eq!(big.ct_eq(&FeBn254::new(BigInt::from(1))), Choice::from(0));
+    }
+
+    #[test]
+    fn test_fermat_inverse() {
+        for i in 1..13 {
+            let a = Fe13::new(i);
+            assert_eq!(a.inverse_ct(), a.inverse());
+        }
+        assert!(Fe13::zero().inverse_ct().is_zero());
+
+        let a: Fe13_4 = Polynomial::from(vec![3, 0, 5, 1]).into();
+        assert_eq!(a.inverse_ct(), a.inverse());
+        let b: Fe43_6 = Polynomial::from(vec![1, 42, 0, 7, 0, 9]).into();
+        assert_eq!(b.inverse_ct(), b.inverse());
+        let c = FeBn254::new(BigInt::from(123456789));
+        assert_eq!(c.inverse_ct(), c.inverse());
+    }
+
+    #[test]
+    fn test_pow_ct() {
+        let a = Fe13::new(3);
+        assert_eq!(a.pow_ct(&BigInt::from(5), 16), Fe13::new(9));
+        assert_eq!(a.pow_ct(&BigInt::from(0), 16), Fe13::one());
+    }
+
+    #[test]
+    fn test_add_complete() {
+        let p = ProjectivePoint::from(TinyJJ::generator());
+        let q = ProjectivePoint::from(TinyJJ::generator() * 5);
+        let inf = ProjectivePoint::<TinyJJ>::infinity();
+
+        assert_eq!(p.add_complete(&q), p.clone() + q.clone());
+        assert_eq!(p.add_complete(&p), p.double());
+        assert_eq!(p.add_complete(&inf), p);
+        assert_eq!(inf.add_complete(&p), p);
+        assert!(p.add_complete(&-p.clone()).is_inf());
+        assert!(inf.add_complete(&inf).is_inf());
+
+        let p = ProjectivePoint::from(MoonMath::generator());
+        let q = ProjectivePoint::from(MoonMath::generator() * 7);
+        assert_eq!(p.add_complete(&q), p.clone() + q);
+        assert_eq!(p.add_complete(&p), p.double());
+    }
+
+    #[test]
+    fn test_mul_ct() {
+        let p = TinyJJ::generator();
+        let order = TinyJJ::order();
+        for k in (0..20).chain([123, -1, -7, order - 1, order, order + 3]) {
+            assert_eq!(p.mul_ct(k), p.clone() * k, "k = {}", k);
+        }
+
+        let p = MoonMath::generator();
+        for k in [0, 1, 2, 13, 987654321, -5, MoonMath::order() - 1] {
+            assert_eq!(p.mul_ct(k), p.clone() * k, "k = {}", k);
+        }
+    }
+}
@@ -1,10 +1,24 @@
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+
 use crate::{
+    ate::{AtePairing, CurveFamily},
     elliptic_curve::{AffinePoint, EllipticCurve},
     field_element::FieldElement,
-    fields::{Ff13, Ff13_4, Ff43_6, Ff43},
+    fields::{
+        Fe13, Fe43, FeBn254, Ff103, Ff103_12, Ff103_2, Ff13, Ff13_2, Ff13_4, Ff43, Ff43_6, FfBn254,
+        FfBn254_12, FfBn254_2,
+    },
     finite_field::FiniteField,
+    glv::GlvCurve,
+    hash_to_curve::HashToCurve,
+    models::{MontgomeryCurve, TwistedEdwardsCurve},
     pairing::Pairing,
+    point_counting,
     polynomial::Polynomial,
+    subfield::SubfieldCurve,
+    twist::Twist,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -24,12 +38,17 @@ impl EllipticCurve for TinyJJ {
 
     fn generator() -> AffinePoint<Self> {
         // 5*x^3 + 12*x^2 + 2*x + 8 and 5*x^2 + x
-        AffinePoint::new_xy(
+        AffinePoint::try_new(
             Polynomial::from(vec![8, 2, 12, 5]).into(),
             Polynomial::from(vec![0, 1, 5]).into(),
         )
+        .unwrap()
     }
 
+    // The embedding degree, order and r of the curves in this file are the
+    // values computed by the default implementations, cached here since
+    // they are needed all the time.
+
     fn embedding_degree() -> usize {
         4
     }
@@ -43,6 +62,174 @@ impl EllipticCurve for TinyJJ {
     }
 }
 
+// y^2 = x^3 + 9x + 2t over F_13^2, the quadratic twist of TinyJJ
+#[derive(Debug, Clone, PartialEq)]
+pub struct TinyJJTwist;
+
+impl EllipticCurve for TinyJJTwist {
+    type BaseField = Ff13_2;
+    type ScalarField = Ff13;
+
+    fn a() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![9]).into()
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![0, 2]).into()
+    }
+
+    fn generator() -> AffinePoint<Self> {
+        // 3*t + 4 and 8*t + 5, untwisted into (4*x^2 + 7, 5*x^3 + 10*x)
+        AffinePoint::try_new(
+            Polynomial::from(vec![4, 3]).into(),
+            Polynomial::from(vec![5, 8]).into(),
+        )
+        .unwrap()
+    }
+
+    fn embedding_degree() -> usize {
+        2
+    }
+
+    fn order() -> <Self::ScalarField as FiniteField>::T {
+        180
+    }
+
+    fn r() -> <Self::ScalarField as FiniteField>::T {
+        5
+    }
+}
+
+impl Twist for TinyJJ {
+    type TwistedCurve = TinyJJTwist;
+
+    fn twist_degree() -> usize {
+        2
+    }
+
+    fn omega() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![0, 1]).into()
+    }
+
+    fn subfield_generator() -> FieldElement<Self::BaseField> {
+        // t^2 = -2 = x^4
+        Polynomial::from(vec![0, 0, 1]).into()
+    }
+}
+
+impl HashToCurve for TinyJJ {}
+
+impl HashToCurve for TinyJJTwist {}
+
+// y^2 = x^3 + 8x + 8 over F_13, the subfield TinyJJ is defined over
+#[derive(Debug, Clone, PartialEq)]
+pub struct TinyJJFp;
+
+impl EllipticCurve for TinyJJFp {
+    type BaseField = Ff13;
+    type ScalarField = Ff13;
+
+    fn a() -> FieldElement<Self::BaseField> {
+        Fe13::new(8)
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        Fe13::new(8)
+    }
+
+    fn generator() -> AffinePoint<Self> {
+        AffinePoint::try_new(Fe13::new(9), Fe13::new(4)).unwrap()
+    }
+
+    fn embedding_degree() -> usize {
+        4
+    }
+
+    fn order() -> <Self::ScalarField as FiniteField>::T {
+        20
+    }
+
+    fn r() -> <Self::ScalarField as FiniteField>::T {
+        5
+    }
+}
+
+impl SubfieldCurve for TinyJJ {
+    type Descended = TinyJJFp;
+}
+
+// TinyJJ in its native twisted Edwards form 3x^2 + y^2 = 1 + 8x^2y^2
+#[derive(Debug, Clone, PartialEq)]
+pub struct TinyJJEdwards;
+
+impl TwistedEdwardsCurve for TinyJJEdwards {
+    type BaseField = Ff13_4;
+    type Montgomery = TinyJJMontgomery;
+
+    fn a() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![3]).into()
+    }
+
+    fn d() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![8]).into()
+    }
+}
+
+// TinyJJ in Montgomery form 7v^2 = u^3 + 6u^2 + u
+#[derive(Debug, Clone, PartialEq)]
+pub struct TinyJJMontgomery;
+
+impl MontgomeryCurve for TinyJJMontgomery {
+    type BaseField = Ff13_4;
+    type Weierstrass = TinyJJ;
+
+    fn a() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![6]).into()
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![7]).into()
+    }
+}
+
+// y^2 = x^3 + x + 4 over F_13^2, a supersingular curve with 14 points over
+// F_13 and 196 = 14^2 over F_13^2, so the whole 7-torsion is defined over it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tiny7;
+
+impl EllipticCurve for Tiny7 {
+    type BaseField = Ff13_2;
+    type ScalarField = Ff13;
+
+    fn a() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![1]).into()
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![4]).into()
+    }
+
+    fn generator() -> AffinePoint<Self> {
+        AffinePoint::try_new(
+            Polynomial::from(vec![0]).into(),
+            Polynomial::from(vec![2]).into(),
+        )
+        .unwrap()
+    }
+
+    fn embedding_degree() -> usize {
+        2
+    }
+
+    fn order() -> <Self::ScalarField as FiniteField>::T {
+        196
+    }
+
+    fn r() -> <Self::ScalarField as FiniteField>::T {
+        7
+    }
+}
+
 // BLS6_6
 #[derive(Debug, Clone, PartialEq)]
 pub struct MoonMath;
@@ -61,10 +248,11 @@ impl EllipticCurve for MoonMath {
 
     fn generator() -> AffinePoint<Self> {
         // 7*v^2, 16*v^3
-        AffinePoint::new_xy(
+        AffinePoint::try_new(
             Polynomial::from(vec![0, 0, 7]).into(),
             Polynomial::from(vec![0, 0, 0, 16]).into(),
         )
+        .unwrap()
     }
 
     fn embedding_degree() -> usize {
@@ -79,3 +267,432 @@ impl EllipticCurve for MoonMath {
         13
     }
 }
+
+impl GlvCurve for MoonMath {
+    fn beta() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![6]).into()
+    }
+
+    fn lambda() -> BigInt {
+        BigInt::from(9)
+    }
+}
+
+impl HashToCurve for MoonMath {}
+
+// y^2 = x^3 + 42 over F_43, the sextic twist of MoonMath
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoonMathTwist;
+
+impl EllipticCurve for MoonMathTwist {
+    type BaseField = Ff43;
+    type ScalarField = Ff43;
+
+    fn a() -> FieldElement<Self::BaseField> {
+        Fe43::new(0)
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        Fe43::new(42)
+    }
+
+    fn generator() -> AffinePoint<Self> {
+        AffinePoint::try_new(Fe43::new(7), Fe43::new(16)).unwrap()
+    }
+
+    fn embedding_degree() -> usize {
+        6
+    }
+
+    fn order() -> <Self::ScalarField as FiniteField>::T {
+        52
+    }
+
+    fn r() -> <Self::ScalarField as FiniteField>::T {
+        13
+    }
+}
+
+impl Twist for MoonMath {
+    type TwistedCurve = MoonMathTwist;
+
+    fn twist_degree() -> usize {
+        6
+    }
+
+    fn omega() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![0, 1]).into()
+    }
+
+    fn subfield_generator() -> FieldElement<Self::BaseField> {
+        FieldElement::one()
+    }
+}
+
+impl HashToCurve for MoonMathTwist {}
+
+// y^2 = x^3 + 6 over F_43, the subfield MoonMath is defined over
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoonMathFp;
+
+impl EllipticCurve for MoonMathFp {
+    type BaseField = Ff43;
+    type ScalarField = Ff43;
+
+    fn a() -> FieldElement<Self::BaseField> {
+        Fe43::new(0)
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        Fe43::new(6)
+    }
+
+    fn generator() -> AffinePoint<Self> {
+        AffinePoint::try_new(Fe43::new(2), Fe43::new(10)).unwrap()
+    }
+
+    fn embedding_degree() -> usize {
+        6
+    }
+
+    fn order() -> <Self::ScalarField as FiniteField>::T {
+        39
+    }
+
+    fn r() -> <Self::ScalarField as FiniteField>::T {
+        13
+    }
+}
+
+impl SubfieldCurve for MoonMath {
+    type Descended = MoonMathFp;
+}
+
+impl AtePairing for MoonMath {
+    // BLS6 with u = 4: r = u^2 - u + 1 = 13 and q = (u - 1)^2 * r / 3 + u = 43
+    fn family() -> CurveFamily {
+        CurveFamily::Bls
+    }
+
+    fn u() -> BigInt {
+        BigInt::from(4)
+    }
+}
+
+// The BN curve with u = 1: p = 36u^4 + 36u^3 + 24u^2 + 6u + 1 = 103 and
+// r = 36u^4 + 36u^3 + 18u^2 + 6u + 1 = 97. y^2 = x^3 + 5 over F_103, with
+// the same construction as BN254 on a scale where everything can be checked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TinyBn;
+
+impl EllipticCurve for TinyBn {
+    type BaseField = Ff103_12;
+    type ScalarField = Ff103;
+
+    fn a() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![0]).into()
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![5]).into()
+    }
+
+    fn generator() -> AffinePoint<Self> {
+        AffinePoint::try_new(
+            Polynomial::from(vec![2]).into(),
+            Polynomial::from(vec![42]).into(),
+        )
+        .unwrap()
+    }
+
+    fn embedding_degree() -> usize {
+        12
+    }
+
+    fn order() -> <Self::ScalarField as FiniteField>::T {
+        1425760886847297042753984
+    }
+
+    fn r() -> <Self::ScalarField as FiniteField>::T {
+        97
+    }
+}
+
+// y^2 = x^3 + 5 / (2 + i) over F_103^2
+#[derive(Debug, Clone, PartialEq)]
+pub struct TinyBnTwist;
+
+impl EllipticCurve for TinyBnTwist {
+    type BaseField = Ff103_2;
+    type ScalarField = Ff103;
+
+    fn a() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![0]).into()
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        // 5 / (2 + i) = 2 - i
+        Polynomial::from(vec![2, 102]).into()
+    }
+
+    fn generator() -> AffinePoint<Self> {
+        AffinePoint::try_new(
+            Polynomial::from(vec![86, 29]).into(),
+            Polynomial::from(vec![88, 47]).into(),
+        )
+        .unwrap()
+    }
+
+    fn embedding_degree() -> usize {
+        6
+    }
+
+    fn order() -> <Self::ScalarField as FiniteField>::T {
+        // r * (2p - r)
+        10573
+    }
+
+    fn r() -> <Self::ScalarField as FiniteField>::T {
+        97
+    }
+}
+
+impl Twist for TinyBn {
+    type TwistedCurve = TinyBnTwist;
+
+    fn twist_degree() -> usize {
+        6
+    }
+
+    fn omega() -> FieldElement<Self::BaseField> {
+        // w^6 = 2 + i
+        Polynomial::from(vec![0, 1]).into()
+    }
+
+    fn subfield_generator() -> FieldElement<Self::BaseField> {
+        // i = w^6 - 2
+        Polynomial::from(vec![-2, 0, 0, 0, 0, 0, 1]).into()
+    }
+}
+
+impl AtePairing for TinyBn {
+    fn family() -> CurveFamily {
+        CurveFamily::Bn
+    }
+
+    fn u() -> BigInt {
+        BigInt::from(1)
+    }
+}
+
+fn bn254_r() -> BigInt {
+    BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap()
+}
+
+fn bn254_trace() -> BigInt {
+    // 6 * u^2 + 1 with u = 4965661367192848881
+    BigInt::from_str("147946756881789318990833708069417712967").unwrap()
+}
+
+// y^2 = x^3 + 3, with G2 on its sextic twist over F_p^2
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bn254;
+
+impl EllipticCurve for Bn254 {
+    type BaseField = FfBn254_12;
+    type ScalarField = FfBn254;
+
+    fn a() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![BigInt::from(0)]).into()
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![BigInt::from(3)]).into()
+    }
+
+    fn generator() -> AffinePoint<Self> {
+        // the G1 generator (1, 2)
+        AffinePoint::try_new(
+            Polynomial::from(vec![BigInt::from(1)]).into(),
+            Polynomial::from(vec![BigInt::from(2)]).into(),
+        )
+        .unwrap()
+    }
+
+    fn embedding_degree() -> usize {
+        12
+    }
+
+    fn order() -> <Self::ScalarField as FiniteField>::T {
+        point_counting::order_over_extension(&FfBn254::modulus(), &bn254_trace(), 12)
+    }
+
+    fn r() -> <Self::ScalarField as FiniteField>::T {
+        bn254_r()
+    }
+}
+
+impl GlvCurve for Bn254 {
+    fn beta() -> FieldElement<Self::BaseField> {
+        let beta = BigInt::from_str(
+            "21888242871839275220042445260109153167277707414472061641714758635765020556616",
+        )
+        .unwrap();
+        Polynomial::from(vec![beta]).into()
+    }
+
+    fn lambda() -> BigInt {
+        BigInt::from_str(
+            "21888242871839275217838484774961031246154997185409878258781734729429964517155",
+        )
+        .unwrap()
+    }
+}
+
+impl HashToCurve for Bn254 {
+    fn h_eff() -> BigInt {
+        // E(F_p) has prime order r
+        BigInt::from(1)
+    }
+}
+
+// y^2 = x^3 + 3 / (9 + i) over F_p^2
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bn254Twist;
+
+impl EllipticCurve for Bn254Twist {
+    type BaseField = FfBn254_2;
+    type ScalarField = FfBn254;
+
+    fn a() -> FieldElement<Self::BaseField> {
+        Polynomial::from(vec![BigInt::from(0)]).into()
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        let three: FieldElement<Self::BaseField> = Polynomial::from(vec![BigInt::from(3)]).into();
+        let xi: FieldElement<Self::BaseField> =
+            Polynomial::from(vec![BigInt::from(9), BigInt::from(1)]).into();
+        three / xi
+    }
+
+    fn generator() -> AffinePoint<Self> {
+        let coordinate = |c0: &str, c1: &str| -> FieldElement<Self::BaseField> {
+            Polynomial::new(vec![
+                FeBn254::new(BigInt::from_str(c0).unwrap()),
+                FeBn254::new(BigInt::from_str(c1).unwrap()),
+            ])
+            .into()
+        };
+        AffinePoint::try_new(
+            coordinate(
+                "10857046999023057135944570762232829481370756359578518086990519993285655852781",
+                "11559732032986387107991004021392285783925812861821192530917403151452391805634",
+            ),
+            coordinate(
+                "8495653923123431417604973247489272438418190587263600148770280649306958101930",
+                "4082367875863433681332203403145435568316851327593401208105741076214120093531",
+            ),
+        )
+        .unwrap()
+    }
+
+    fn embedding_degree() -> usize {
+        6
+    }
+
+    fn order() -> <Self::ScalarField as FiniteField>::T {
+        // r * (2p - r)
+        let r = bn254_r();
+        (FfBn254::modulus() * 2 - &r) * r
+    }
+
+    fn r() -> <Self::ScalarField as FiniteField>::T {
+        bn254_r()
+    }
+}
+
+impl Twist for Bn254 {
+    type TwistedCurve = Bn254Twist;
+
+    fn twist_degree() -> usize {
+        6
+    }
+
+    fn omega() -> FieldElement<Self::BaseField> {
+        // w^6 = 9 + i
+        Polynomial::from(vec![BigInt::from(0), BigInt::from(1)]).into()
+    }
+
+    fn subfield_generator() -> FieldElement<Self::BaseField> {
+        // i = w^6 - 9
+        let mut coeffs = vec![BigInt::from(0); 7];
+        coeffs[0] = BigInt::from(-9);
+        coeffs[6] = BigInt::from(1);
+        Polynomial::from(coeffs).into()
+    }
+}
+
+impl AtePairing for Bn254 {
+    fn family() -> CurveFamily {
+        CurveFamily::Bn
+    }
+
+    fn u() -> BigInt {
+        BigInt::from(4965661367192848881u64)
+    }
+}
+
+impl HashToCurve for Bn254Twist {
+    fn h_eff() -> BigInt {
+        FfBn254::modulus() * 2 - bn254_r()
+    }
+
+    // [u]Q + ψ([3u]Q) + ψ^2([u]Q) + ψ^3(Q), the map of Fuentes-Castañeda,
+    // Knapp and Rodríguez-Henríquez the published vectors are computed with
+    fn clear_cofactor(q: &AffinePoint<Self>) -> AffinePoint<Self> {
+        let uq = q.mul_double_and_add(&Bn254::u());
+        let psi2_uq = Bn254::psi(&Bn254::psi(&uq));
+        let psi3_q = Bn254::psi(&Bn254::psi(&Bn254::psi(q)));
+        uq.clone() + Bn254::psi(&uq.mul_double_and_add(&BigInt::from(3))) + psi2_uq + psi3_q
+    }
+}
+
+// y^2 = x^3 + 3 over F_p, where G1 lives
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bn254Fp;
+
+impl EllipticCurve for Bn254Fp {
+    type BaseField = FfBn254;
+    type ScalarField = FfBn254;
+
+    fn a() -> FieldElement<Self::BaseField> {
+        FeBn254::new(BigInt::from(0))
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        FeBn254::new(BigInt::from(3))
+    }
+
+    fn generator() -> AffinePoint<Self> {
+        AffinePoint::try_new(FeBn254::new(BigInt::from(1)), FeBn254::new(BigInt::from(2))).unwrap()
+    }
+
+    fn embedding_degree() -> usize {
+        12
+    }
+
+    fn order() -> <Self::ScalarField as FiniteField>::T {
+        bn254_r()
+    }
+
+    fn r() -> <Self::ScalarField as FiniteField>::T {
+        bn254_r()
+    }
+}
+
+impl SubfieldCurve for Bn254 {
+    type Descended = Bn254Fp;
+}
@@ -1,17 +1,23 @@
 use std::{
+    any::type_name,
+    fmt::{self, Display},
     ops::{Add, Mul, Neg},
-    usize,
 };
 
-use num_traits::Pow;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Pow, Signed, ToPrimitive, Zero};
 
 use crate::{
     field_element::FieldElement,
-    finite_field::{FiniteField, NonExtendedField},
+    finite_field::{check_once, FiniteField, NonExtendedField},
+    number_theory::{factor, is_prime, largest_prime_factor, multiplicative_order},
     pairing::Pairing,
+    point_counting,
+    validation::{self, ValidationError},
 };
 
-pub trait EllipticCurve: Clone + PartialEq {
+pub trait EllipticCurve: Clone + PartialEq + 'static {
     // the base field is typically an extension field
     type BaseField: FiniteField;
     type ScalarField: NonExtendedField;
@@ -19,18 +25,111 @@ pub trait EllipticCurve: Clone + PartialEq {
     fn a() -> FieldElement<Self::BaseField>;
     fn b() -> FieldElement<Self::BaseField>;
 
-    fn embedding_degree() -> usize;
     fn generator() -> AffinePoint<Self>;
 
-    // number of points in the curve, defined over
-    // the base field
-    fn order() -> <Self::ScalarField as FiniteField>::T;
+    /// The embedding degree: the smallest k with r | q^k - 1,
+    /// where F_q is the field the curve is defined over.
+    fn embedding_degree() -> usize {
+        let (q, _) = point_counting::field_of_definition::<Self>();
+        let r = Self::ScalarField::to_bigint(&Self::r());
+        multiplicative_order(&q, &r)
+            .and_then(|k| k.to_usize())
+            .expect("r divides q, so there is no embedding degree")
+    }
+
+    /// The number of points of the curve over the base field,
+    /// computed by point counting unless overridden.
+    fn order() -> <Self::ScalarField as FiniteField>::T {
+        Self::ScalarField::from_bigint(&point_counting::curve_order::<Self>())
+            .expect("the order does not fit in the scalar field type")
+    }
 
-    // this biggest cofactor of the order of the curve,
-    // defined over the non-extended field
-    fn r() -> <Self::ScalarField as FiniteField>::T;
+    /// The largest prime factor of the number of points over the
+    /// field the curve is defined over.
+    fn r() -> <Self::ScalarField as FiniteField>::T {
+        let order = point_counting::definition_field_order::<Self>();
+        let r = largest_prime_factor(&order).expect("the curve has a single point");
+        Self::ScalarField::from_bigint(&r).unwrap()
+    }
+
+    /// The cofactor h = #E / r^2 of the r-torsion, assuming the base field
+    /// is large enough for all of it to be defined over it.
+    fn cofactor() -> <Self::ScalarField as FiniteField>::T {
+        let order = Self::ScalarField::to_bigint(&Self::order());
+        let r = Self::ScalarField::to_bigint(&Self::r());
+        let (cofactor, remainder) = order.div_rem(&(&r * &r));
+        assert!(
+            remainder.is_zero(),
+            "r^2 does not divide the order, so the r-torsion is not all defined over the base field"
+        );
+        Self::ScalarField::from_bigint(&cofactor).unwrap()
+    }
+
+    /// The discriminant -16(4a^3 + 27b^2), zero for singular curves.
+    fn discriminant() -> FieldElement<Self::BaseField> {
+        let constant =
+            |n: i64| FieldElement::new(Self::BaseField::from_coefficients(&[BigInt::from(n)]));
+        let (a, b) = (Self::a(), Self::b());
+        constant(-16) * (constant(4) * a.clone() * a.clone() * a + constant(27) * b.clone() * b)
+    }
+
+    /// The j-invariant -1728 * (4a)^3 / Δ, or None for a singular curve.
+    fn j_invariant() -> Option<FieldElement<Self::BaseField>> {
+        let constant =
+            |n: i64| FieldElement::new(Self::BaseField::from_coefficients(&[BigInt::from(n)]));
+        let discriminant = Self::discriminant();
+        if discriminant.is_zero() {
+            return None;
+        }
+        let four_a = constant(4) * Self::a();
+        Some(constant(-1728) * four_a.clone() * four_a.clone() * four_a / discriminant)
+    }
+
+    /// Checks that the field characteristic is prime and that
+    /// r is a prime divisor of the order of the curve.
+    fn has_valid_r() -> bool {
+        let order = Self::ScalarField::to_bigint(&Self::order());
+        let r = Self::ScalarField::to_bigint(&Self::r());
+        Self::ScalarField::is_prime_modulus() && is_prime(&r) && (order % r).is_zero()
+    }
+
+    /// Panics unless r is a prime divisor of the order. The check runs the
+    /// first time a point of the curve is built, and is skipped afterwards.
+    fn assert_valid_r() {
+        check_once::<Self>(|| {
+            assert!(
+                Self::has_valid_r(),
+                "r is not a prime divisor of the order of {}",
+                type_name::<Self>()
+            )
+        });
+    }
+
+    /// Checks the parameters of the curve, returning every failed check.
+    fn validate() -> Result<(), Vec<ValidationError>> {
+        validation::validate::<Self>()
+    }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveError {
+    /// The coordinates do not satisfy the curve equation.
+    NotOnCurve,
+    /// The point is on the curve, but not in the requested subgroup.
+    NotInSubgroup,
+}
+
+impl Display for CurveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CurveError::NotOnCurve => write!(f, "point is not on the curve"),
+            CurveError::NotInSubgroup => write!(f, "point is not in the subgroup"),
+        }
+    }
+}
+
+impl std::error::Error for CurveError {}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum AffinePoint<E: EllipticCurve> {
     Infinity,
@@ -38,15 +137,28 @@ pub enum AffinePoint<E: EllipticCurve> {
 }
 
 impl<E: EllipticCurve> AffinePoint<E> {
-    pub fn new_xy(x: FieldElement<E::BaseField>, y: FieldElement<E::BaseField>) -> Self {
-        // check coordinates are valid, if not return Infinity
+    /// Creates a point from its coordinates, checking that they lie on the curve.
+    pub fn try_new(
+        x: FieldElement<E::BaseField>,
+        y: FieldElement<E::BaseField>,
+    ) -> Result<Self, CurveError> {
+        E::assert_valid_r();
         if Self::is_on_curve(&x, &y) {
-            AffinePoint::XY(x, y)
+            Ok(AffinePoint::XY(x, y))
         } else {
-            Self::new_inf()
+            Err(CurveError::NotOnCurve)
         }
     }
 
+    /// Creates a point without checking the coordinates. Only for results
+    /// of operations that are known to stay on the curve.
+    pub(crate) fn new_unchecked(
+        x: FieldElement<E::BaseField>,
+        y: FieldElement<E::BaseField>,
+    ) -> Self {
+        AffinePoint::XY(x, y)
+    }
+
     pub fn new_inf() -> Self {
         AffinePoint::Infinity
     }
@@ -56,6 +168,7 @@ impl<E: EllipticCurve> AffinePoint<E> {
     }
 
     /// Returns the x and y coordinates of this affine point.
+    #[allow(clippy::type_complexity)]
     pub fn xy(&self) -> Option<(FieldElement<E::BaseField>, FieldElement<E::BaseField>)> {
         match self {
             AffinePoint::XY(x, y) => Some((x.clone(), y.clone())),
@@ -95,12 +208,29 @@ impl<E: EllipticCurve> AffinePoint<E> {
                 let new_x = m.clone() * m.clone() - x.clone() - x.clone();
                 let new_y = m.clone() * (x.clone() - new_x.clone()) - y.clone();
 
-                AffinePoint::new_xy(new_x, new_y)
+                AffinePoint::new_unchecked(new_x, new_y)
             }
             _ => AffinePoint::Infinity,
         }
     }
 
+    /// The Frobenius endomorphism π^i(x, y) = (x^(q^i), y^(q^i)), where F_q
+    /// is the field the curve is defined over.
+    pub fn frobenius(&self, i: usize) -> Self {
+        match self {
+            AffinePoint::XY(x, y) => {
+                let (q, _) = point_counting::field_of_definition::<E>();
+                let (mut x, mut y) = (x.clone(), y.clone());
+                for _ in 0..i {
+                    x = x.pow_bigint(&q);
+                    y = y.pow_bigint(&q);
+                }
+                AffinePoint::new_unchecked(x, y)
+            }
+            AffinePoint::Infinity => AffinePoint::Infinity,
+        }
+    }
+
     pub fn trace_map(&self) -> Self {
         match self {
             AffinePoint::XY(x, y) => {
@@ -109,13 +239,20 @@ impl<E: EllipticCurve> AffinePoint<E> {
                     let power = E::ScalarField::modulus().pow(i);
                     let new_x = x.pow::<E::ScalarField>(power.clone());
                     let new_y = y.pow::<E::ScalarField>(power);
-                    point = point + AffinePoint::new_xy(new_x, new_y);
+                    point = point + AffinePoint::new_unchecked(new_x, new_y);
                 }
                 point
             }
             _ => AffinePoint::Infinity,
         }
     }
+
+    /// The anti-trace map k * P - Tr(P), whose image has trace zero.
+    /// Applied to an r-torsion point, it lands in G2.
+    pub fn anti_trace_map(&self) -> Self {
+        let k = E::ScalarField::from_uint(E::embedding_degree()).unwrap();
+        self.clone() * k + -self.trace_map()
+    }
 }
 
 impl<E: EllipticCurve> Add for AffinePoint<E> {
@@ -142,26 +279,38 @@ impl<E: EllipticCurve> Add for AffinePoint<E> {
     }
 }
 
+impl<E: EllipticCurve> AffinePoint<E> {
+    /// Double-and-Add algorithm over the bits of |k|. A negative k
+    /// multiplies the negated point, and k = 0 gives the point at infinity.
+    pub fn mul_double_and_add(&self, k: &BigInt) -> Self {
+        let magnitude = k.magnitude();
+        if magnitude.is_zero() || self.is_inf() {
+            return AffinePoint::Infinity;
+        }
+
+        let mut point = self.clone();
+        for i in (0..magnitude.bits() - 1).rev() {
+            point = point.double();
+
+            if magnitude.bit(i) {
+                point = point + self.clone();
+            }
+        }
+
+        if k.is_negative() {
+            -point
+        } else {
+            point
+        }
+    }
+}
+
 /// Multiplication by a scalar
 impl<E: EllipticCurve> Mul<<E::ScalarField as FiniteField>::T> for AffinePoint<E> {
     type Output = AffinePoint<E>;
 
     fn mul(self, scalar: <E::ScalarField as FiniteField>::T) -> Self::Output {
-        match self {
-            AffinePoint::XY(x, y) => {
-                let mut point = Self::XY(x.clone(), y.clone());
-                // Double-and-Add algorithm
-                for bit in E::ScalarField::to_bits(scalar).iter().skip(1) {
-                    point = point.double();
-
-                    if *bit {
-                        point = point + Self::XY(x.clone(), y.clone());
-                    }
-                }
-                point
-            }
-            _ => AffinePoint::Infinity,
-        }
+        self.mul_double_and_add(&E::ScalarField::to_bigint(&scalar))
     }
 }
 
@@ -177,33 +326,315 @@ impl<E: EllipticCurve> Neg for AffinePoint<E> {
 }
 
 impl<E: EllipticCurve + Pairing> AffinePoint<E> {
+    /// Creates a point, checking that it lies on the curve and in G1,
+    /// the subgroup of points of order r fixed by the Frobenius map.
+    pub fn try_new_g1(
+        x: FieldElement<E::BaseField>,
+        y: FieldElement<E::BaseField>,
+    ) -> Result<Self, CurveError> {
+        let p = Self::try_new(x, y)?;
+        if E::is_valid_g1(&p) && (p.clone() * E::r()).is_inf() {
+            Ok(p)
+        } else {
+            Err(CurveError::NotInSubgroup)
+        }
+    }
+
+    /// Creates a point, checking that it lies on the curve and in G2,
+    /// the subgroup of points of order r with trace zero.
+    pub fn try_new_g2(
+        x: FieldElement<E::BaseField>,
+        y: FieldElement<E::BaseField>,
+    ) -> Result<Self, CurveError> {
+        let q = Self::try_new(x, y)?;
+        if E::is_valid_g2(&q) && (q.clone() * E::r()).is_inf() {
+            Ok(q)
+        } else {
+            Err(CurveError::NotInSubgroup)
+        }
+    }
+
     pub fn pairing(&self, q: &Self) -> FieldElement<E::BaseField> {
         E::tate_pairing(self, q)
     }
 }
 
+/// Lazily lists every point of the curve over the base field: the point at
+/// infinity first, then for each x in the order of `FieldElement::elements`
+/// the points (x, y) and (x, -y), with sgn0(y) = 0 first.
+pub fn points<E: EllipticCurve>() -> impl Iterator<Item = AffinePoint<E>> {
+    let affine = FieldElement::<E::BaseField>::elements().flat_map(|x| {
+        let y2 = x.clone() * x.clone() * x.clone() + E::a() * x.clone() + E::b();
+        let roots = match y2.sqrt() {
+            Some(y) if y.is_zero() => vec![y],
+            Some(y) if y.sgn0() => vec![-y.clone(), y],
+            Some(y) => vec![y.clone(), -y],
+            None => vec![],
+        };
+        roots
+            .into_iter()
+            .map(move |y| AffinePoint::new_unchecked(x.clone(), y))
+    });
+    std::iter::once(AffinePoint::Infinity).chain(affine)
+}
+
 pub fn get_all_points<E: EllipticCurve>() -> Vec<AffinePoint<E>> {
-    let max = E::ScalarField::to_uint(E::order()).unwrap();
-    let mut result = vec![E::generator(); max];
-    let mut acc = E::generator();
-    for i in (1..max).into_iter() {
-        acc = acc.clone() + E::generator();
-        result[i] = acc.clone();
-    }
-    result
+    points::<E>().collect()
+}
+
+/// The order of a point, given a multiple n of it: every prime factor
+/// of n is removed for as long as the point still vanishes.
+fn order_dividing<E: EllipticCurve>(point: &AffinePoint<E>, n: &BigInt) -> BigInt {
+    let mut order = n.clone();
+    for (p, _) in factor(n) {
+        while order.is_multiple_of(&p) && point.mul_double_and_add(&(&order / &p)).is_inf() {
+            order /= &p;
+        }
+    }
+    order
+}
+
+impl<E: EllipticCurve> AffinePoint<E> {
+    /// The order of the point in the group of points over the base field.
+    pub fn point_order(&self) -> BigInt {
+        order_dividing(self, &E::ScalarField::to_bigint(&E::order()))
+    }
+}
+
+/// The structure Z_n1 x Z_n2 of the group of points over the base field,
+/// with n1 | n2, found by enumerating all the points. n2 is the exponent of
+/// the group, i.e. the largest order of a point. Only practical for tiny
+/// fields.
+pub fn group_structure<E: EllipticCurve>() -> (BigInt, BigInt) {
+    let all = get_all_points::<E>();
+    let n = BigInt::from(all.len());
+
+    // the exponent is the lcm of the orders, so only the points it does not
+    // already kill need their order computed
+    let mut exponent = BigInt::one();
+    for point in &all {
+        if exponent == n {
+            break;
+        }
+        if !point.mul_double_and_add(&exponent).is_inf() {
+            exponent = exponent.lcm(&order_dividing(point, &n));
+        }
+    }
+    (&n / &exponent, exponent)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{curves::TinyJJ, fields::Fe13_4, polynomial::Polynomial};
+    use crate::{
+        curves::{MoonMath, Tiny7, TinyJJ},
+        fields::{Fe13, Fe13_4, Fe43_6, Ff13, Ff13_4, Ff43, Ff43_6},
+        polynomial::Polynomial,
+    };
 
     use super::*;
 
-    //#[test]
-    //fn test_ec_all_points() {
-    //let points = get_all_points::<TinyJJ>();
-    //assert!(points.len() == TinyJJ::order().try_into().unwrap());
-    //}
+    /// TinyJJ relying on the default implementations.
+    #[derive(Debug, Clone, PartialEq)]
+    struct TinyJJDefaults;
+
+    impl EllipticCurve for TinyJJDefaults {
+        type BaseField = Ff13_4;
+        type ScalarField = Ff13;
+
+        fn a() -> Fe13_4 {
+            TinyJJ::a()
+        }
+
+        fn b() -> Fe13_4 {
+            TinyJJ::b()
+        }
+
+        fn generator() -> AffinePoint<Self> {
+            let (x, y) = TinyJJ::generator().xy().unwrap();
+            AffinePoint::try_new(x, y).unwrap()
+        }
+    }
+
+    /// MoonMath relying on the default implementations.
+    #[derive(Debug, Clone, PartialEq)]
+    struct MoonMathDefaults;
+
+    impl EllipticCurve for MoonMathDefaults {
+        type BaseField = Ff43_6;
+        type ScalarField = Ff43;
+
+        fn a() -> Fe43_6 {
+            MoonMath::a()
+        }
+
+        fn b() -> Fe43_6 {
+            MoonMath::b()
+        }
+
+        fn generator() -> AffinePoint<Self> {
+            let (x, y) = MoonMath::generator().xy().unwrap();
+            AffinePoint::try_new(x, y).unwrap()
+        }
+    }
+
+    /// TinyJJ's equation over the prime field, with a cyclic group of 20 points.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Tiny13;
+
+    impl EllipticCurve for Tiny13 {
+        type BaseField = Ff13;
+        type ScalarField = Ff13;
+
+        fn a() -> Fe13 {
+            Fe13::new(8)
+        }
+
+        fn b() -> Fe13 {
+            Fe13::new(8)
+        }
+
+        fn generator() -> AffinePoint<Self> {
+            AffinePoint::try_new(Fe13::new(9), Fe13::new(4)).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_ec_default_parameters() {
+        assert_eq!(TinyJJDefaults::order(), TinyJJ::order());
+        assert_eq!(TinyJJDefaults::r(), TinyJJ::r());
+        assert_eq!(
+            TinyJJDefaults::embedding_degree(),
+            TinyJJ::embedding_degree()
+        );
+        // 28800 = 5^2 * 1152
+        assert_eq!(TinyJJDefaults::cofactor(), 1152);
+        assert_eq!(TinyJJDefaults::validate(), Ok(()));
+
+        assert_eq!(MoonMathDefaults::order(), MoonMath::order());
+        assert_eq!(MoonMathDefaults::r(), MoonMath::r());
+        assert_eq!(
+            MoonMathDefaults::embedding_degree(),
+            MoonMath::embedding_degree()
+        );
+        // 6321251664 = 13^2 * 37403856
+        assert_eq!(MoonMathDefaults::cofactor(), 37403856);
+        assert_eq!(MoonMathDefaults::validate(), Ok(()));
+    }
+
+    #[test]
+    #[should_panic(expected = "r^2 does not divide the order")]
+    fn test_ec_cofactor_partial_torsion() {
+        // 20 = 5 * 4, so only part of the 5-torsion is defined over F_13
+        Tiny13::cofactor();
+    }
+
+    #[test]
+    fn test_ec_all_points() {
+        let points = get_all_points::<Tiny13>();
+        assert_eq!(points.len(), 20);
+        assert_eq!(points[0], AffinePoint::Infinity);
+        assert_eq!(
+            points[1..4],
+            [
+                AffinePoint::try_new(Fe13::new(1), Fe13::new(2)).unwrap(),
+                AffinePoint::try_new(Fe13::new(1), Fe13::new(11)).unwrap(),
+                AffinePoint::try_new(Fe13::new(4), Fe13::new(0)).unwrap(),
+            ]
+        );
+        assert!(points[1..].iter().all(|p| p
+            .xy()
+            .is_some_and(|(x, y)| AffinePoint::<Tiny13>::is_on_curve(&x, &y))));
+
+        // 13^2 + 1 - (-2 * 13) points by Hasse-Weil
+        assert_eq!(super::points::<Tiny7>().count(), 196);
+    }
+
+    #[test]
+    fn test_ec_point_order() {
+        assert_eq!(Tiny13::generator().point_order(), BigInt::from(20));
+        let p = AffinePoint::<Tiny13>::try_new(Fe13::new(1), Fe13::new(2)).unwrap();
+        assert_eq!(p.point_order(), BigInt::from(10));
+        let p = AffinePoint::<Tiny13>::try_new(Fe13::new(4), Fe13::new(0)).unwrap();
+        assert_eq!(p.point_order(), BigInt::from(2));
+        assert_eq!(AffinePoint::<Tiny13>::Infinity.point_order(), BigInt::one());
+    }
+
+    #[test]
+    fn test_ec_group_structure() {
+        assert_eq!(
+            group_structure::<Tiny13>(),
+            (BigInt::from(1), BigInt::from(20))
+        );
+        // supersingular over F_13^2: the group is Z_14 x Z_14
+        assert_eq!(
+            group_structure::<Tiny7>(),
+            (BigInt::from(14), BigInt::from(14))
+        );
+    }
+
+    #[test]
+    fn test_ec_tinyjj_group_structure() {
+        assert_eq!(super::points::<TinyJJ>().count(), 28800);
+        // the whole 5-torsion is defined over F_13^4, so the group is not
+        // cyclic: 60 | 13^4 - 1 and 60 * 480 = 28800
+        assert_eq!(
+            group_structure::<TinyJJ>(),
+            (BigInt::from(60), BigInt::from(480))
+        );
+    }
+
+    #[test]
+    fn test_ec_try_new() {
+        let p = AffinePoint::<TinyJJ>::try_new(
+            Polynomial::from(vec![8]).into(),
+            Polynomial::from(vec![8]).into(),
+        );
+        assert!(p.is_ok());
+
+        // a typo in the y coordinate
+        let p = AffinePoint::<TinyJJ>::try_new(
+            Polynomial::from(vec![8]).into(),
+            Polynomial::from(vec![9]).into(),
+        );
+        assert_eq!(p, Err(CurveError::NotOnCurve));
+    }
+
+    #[test]
+    fn test_ec_try_new_subgroups() {
+        let (x, y) = TinyJJ::generator().xy().unwrap();
+        assert_eq!(
+            AffinePoint::<TinyJJ>::try_new_g1(x.clone(), y.clone()),
+            Err(CurveError::NotInSubgroup)
+        );
+        assert_eq!(
+            AffinePoint::<TinyJJ>::try_new_g2(x, y),
+            Err(CurveError::NotInSubgroup)
+        );
+
+        let x: Fe13_4 = Polynomial::from(vec![8]).into();
+        let y: Fe13_4 = Polynomial::from(vec![8]).into();
+        assert!(AffinePoint::<TinyJJ>::try_new_g1(x.clone(), y.clone()).is_ok());
+        assert_eq!(
+            AffinePoint::<TinyJJ>::try_new_g2(x, y),
+            Err(CurveError::NotInSubgroup)
+        );
+
+        let x: Fe13_4 = Polynomial::from(vec![7, 0, 4]).into();
+        let y: Fe13_4 = Polynomial::from(vec![0, 10, 0, 5]).into();
+        assert!(AffinePoint::<TinyJJ>::try_new_g2(x.clone(), y.clone()).is_ok());
+        assert_eq!(
+            AffinePoint::<TinyJJ>::try_new_g1(x, y),
+            Err(CurveError::NotInSubgroup)
+        );
+
+        // on the curve and defined over F_13, but of order 2
+        let x: Fe13_4 = Polynomial::from(vec![4]).into();
+        let y: Fe13_4 = Polynomial::from(vec![0]).into();
+        assert_eq!(
+            AffinePoint::<TinyJJ>::try_new_g1(x, y),
+            Err(CurveError::NotInSubgroup)
+        );
+    }
 
     #[test]
     fn test_ec_generator() {
@@ -213,10 +644,11 @@ mod tests {
 
     #[test]
     fn test_ec_scalar_mul() {
-        let p1 = AffinePoint::<TinyJJ>::new_xy(
+        let p1 = AffinePoint::<TinyJJ>::try_new(
             Polynomial::from(vec![8]).into(),
             Polynomial::from(vec![8]).into(),
-        );
+        )
+        .unwrap();
         let x: Fe13_4 = Polynomial::from(vec![7]).into();
         let y: Fe13_4 = Polynomial::from(vec![2]).into();
         let prod = p1.clone() * 123;
@@ -229,14 +661,16 @@ mod tests {
 
     #[test]
     fn test_ec_add() {
-        let p1 = AffinePoint::<TinyJJ>::new_xy(
+        let p1 = AffinePoint::<TinyJJ>::try_new(
             Polynomial::from(vec![7]).into(),
             Polynomial::from(vec![11]).into(),
-        );
-        let p2 = AffinePoint::<TinyJJ>::new_xy(
+        )
+        .unwrap();
+        let p2 = AffinePoint::<TinyJJ>::try_new(
             Polynomial::from(vec![12]).into(),
             Polynomial::from(vec![8]).into(),
-        );
+        )
+        .unwrap();
         let x: Fe13_4 = Polynomial::from(vec![11]).into();
         let y: Fe13_4 = Polynomial::from(vec![7]).into();
         let sum = p1 + p2;
@@ -246,10 +680,11 @@ mod tests {
 
     #[test]
     fn test_ec_neg() {
-        let p1 = AffinePoint::<TinyJJ>::new_xy(
+        let p1 = AffinePoint::<TinyJJ>::try_new(
             Polynomial::from(vec![8]).into(),
             Polynomial::from(vec![8]).into(),
-        );
+        )
+        .unwrap();
         let neg = -p1;
         let x: Fe13_4 = Polynomial::from(vec![8]).into();
         let y: Fe13_4 = Polynomial::from(vec![5]).into();
@@ -259,20 +694,22 @@ mod tests {
 
     #[test]
     fn test_ec_add_to_inf() {
-        let p1 = AffinePoint::<TinyJJ>::new_xy(
+        let p1 = AffinePoint::<TinyJJ>::try_new(
             Polynomial::from(vec![8]).into(),
             Polynomial::from(vec![8]).into(),
-        );
+        )
+        .unwrap();
         let sum = p1.clone() + -p1;
         assert!(sum.is_inf());
     }
 
     #[test]
     fn test_ec_double() {
-        let p1 = AffinePoint::<TinyJJ>::new_xy(
+        let p1 = AffinePoint::<TinyJJ>::try_new(
             Polynomial::from(vec![7]).into(),
             Polynomial::from(vec![11]).into(),
-        );
+        )
+        .unwrap();
         let x: Fe13_4 = Polynomial::from(vec![8]).into();
         let y: Fe13_4 = Polynomial::from(vec![5]).into();
         let sum = p1.double();
@@ -288,14 +725,41 @@ mod tests {
 
     #[test]
     fn test_ec_trace_map() {
-        let p1 = AffinePoint::<TinyJJ>::new_xy(
+        let p1 = AffinePoint::<TinyJJ>::try_new(
             Polynomial::from(vec![8]).into(),
             Polynomial::from(vec![8]).into(),
-        );
+        )
+        .unwrap();
         let x: Fe13_4 = Polynomial::from(vec![8]).into();
         let y: Fe13_4 = Polynomial::from(vec![5]).into();
         let tm = p1.trace_map();
         assert!(tm.x().unwrap() == x);
         assert!(tm.y().unwrap() == y);
     }
+
+    #[test]
+    fn test_ec_frobenius() {
+        let p = AffinePoint::<TinyJJ>::try_new(
+            Polynomial::from(vec![8]).into(),
+            Polynomial::from(vec![8]).into(),
+        )
+        .unwrap();
+        assert_eq!(p.frobenius(1), p);
+
+        let q = AffinePoint::<TinyJJ>::try_new(
+            Polynomial::from(vec![7, 0, 4]).into(),
+            Polynomial::from(vec![0, 10, 0, 5]).into(),
+        )
+        .unwrap();
+        // π has order k = 4 and sums up to the trace map
+        assert_eq!(q.frobenius(4), q);
+        let trace = (1..4).fold(q.clone(), |acc, i| acc + q.frobenius(i));
+        assert_eq!(trace, q.trace_map());
+        // π(Q) = [13]Q on G2
+        assert_eq!(q.frobenius(1), q.mul_double_and_add(&BigInt::from(13)));
+        assert_eq!(
+            AffinePoint::<TinyJJ>::Infinity.frobenius(1),
+            AffinePoint::Infinity
+        );
+    }
 }
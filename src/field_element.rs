@@ -4,6 +4,10 @@ use std::{
     ops::{Add, Div, Mul, Neg, Sub},
 };
 
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
 use crate::finite_field::{FiniteField, NonExtendedField};
 
 #[derive(Clone, Eq, PartialEq)]
@@ -11,6 +15,14 @@ pub struct FieldElement<M: FiniteField>(M::T);
 
 impl<M: FiniteField> FieldElement<M> {
     pub fn new(value: M::T) -> Self {
+        M::assert_valid_modulus();
+        Self::reduced(value)
+    }
+
+    /// Builds the constants of the field and the results of operations on
+    /// elements, which already went through the check of the modulus in
+    /// `new`.
+    fn reduced(value: M::T) -> Self {
         FieldElement(M::reduce(value))
     }
 
@@ -19,7 +31,7 @@ impl<M: FiniteField> FieldElement<M> {
     }
 
     pub fn zero() -> Self {
-        Self::new(M::zero())
+        Self::reduced(M::zero())
     }
 
     pub fn is_zero(&self) -> bool {
@@ -27,11 +39,11 @@ impl<M: FiniteField> FieldElement<M> {
     }
 
     pub fn one() -> Self {
-        Self::new(M::one())
+        Self::reduced(M::one())
     }
 
     pub fn inverse(&self) -> Self {
-        Self::new(M::inverse(&self.0))
+        Self::reduced(M::inverse(&self.0))
     }
 
     // Exponentiation is computed with the square-and-multiply algorithm,
@@ -55,6 +67,128 @@ impl<M: FiniteField> FieldElement<M> {
 
         acc
     }
+
+    /// Square-and-multiply with an arbitrary integer exponent.
+    /// Negative exponents raise the inverse of the element.
+    pub fn pow_bigint(&self, exp: &BigInt) -> Self {
+        let base = if exp.is_negative() {
+            self.inverse()
+        } else {
+            self.clone()
+        };
+        let exp = exp.magnitude();
+
+        let mut acc = Self::one();
+        for i in (0..exp.bits()).rev() {
+            acc = acc.clone() * acc.clone();
+            if exp.bit(i) {
+                acc = acc * base.clone();
+            }
+        }
+        acc
+    }
+
+    /// Euler's criterion: a non-zero a is a square exactly when a^((q-1)/2) = 1,
+    /// where q is the size of the field.
+    pub fn is_square(&self) -> bool {
+        let exp = (M::size() - 1u32) >> 1;
+        self.is_zero() || self.pow_bigint(&exp) == Self::one()
+    }
+
+    /// The sign of the element as defined in RFC 9380: the parity of
+    /// its first non-zero coefficient over the prime subfield.
+    pub fn sgn0(&self) -> bool {
+        M::to_coefficients(&self.0)
+            .iter()
+            .find(|c| !c.is_zero())
+            .is_some_and(|c| c.is_odd())
+    }
+
+    /// Computes a square root with the Tonelli-Shanks algorithm,
+    /// which works for any field of odd characteristic.
+    /// Returns None when the element is not a square.
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(Self::zero());
+        }
+        if !self.is_square() {
+            return None;
+        }
+
+        // q - 1 = 2^s * t with t odd
+        let q_minus_one = M::size() - 1u32;
+        let s = q_minus_one.trailing_zeros().unwrap();
+        let t: BigInt = &q_minus_one >> s;
+
+        let mut m = s;
+        let mut c = Self::non_square().pow_bigint(&t);
+        let mut b = self.pow_bigint(&t);
+        let mut x = self.pow_bigint(&((&t + 1u32) >> 1));
+
+        // invariant: x^2 = a * b, with b of order dividing 2^(m - 1)
+        while b != Self::one() {
+            let mut i = 0;
+            let mut b_pow = b.clone();
+            while b_pow != Self::one() {
+                b_pow = b_pow.clone() * b_pow;
+                i += 1;
+            }
+
+            let mut g = c;
+            for _ in 0..m - i - 1 {
+                g = g.clone() * g;
+            }
+            x = x * g.clone();
+            c = g.clone() * g;
+            b = b * c.clone();
+            m = i;
+        }
+        Some(x)
+    }
+
+    /// Whether the element lies in the prime subfield F_p.
+    pub fn is_in_prime_subfield(&self) -> bool {
+        M::to_coefficients(&self.0)[1..].iter().all(|c| c.is_zero())
+    }
+
+    /// The first non-square of the field in the order of `elements`.
+    /// In an extension of even degree every element of F_p is a square,
+    /// so the search starts right after the prime subfield.
+    pub fn non_square() -> Self {
+        let start = if M::degree() % 2 == 0 {
+            M::characteristic()
+        } else {
+            BigInt::from(2)
+        };
+        let size = M::size();
+        std::iter::successors(Some(start), |n| Some(n + 1u32))
+            .take_while(|n| *n < size)
+            .map(|n| Self::from_index(&n))
+            .find(|candidate| !candidate.is_square())
+            .expect("every element of the field is a square")
+    }
+
+    /// The n-th element of the field, reading n in base p as the
+    /// coefficients over the prime subfield, lowest degree first.
+    pub fn from_index(n: &BigInt) -> Self {
+        let p = M::characteristic();
+        let mut digits = vec![];
+        let mut rest = n.clone();
+        while !rest.is_zero() {
+            let (q, r) = rest.div_rem(&p);
+            digits.push(r);
+            rest = q;
+        }
+        Self::new(M::from_coefficients(&digits))
+    }
+
+    /// Iterates over all elements of the field, starting with 0 and 1.
+    pub fn elements() -> impl Iterator<Item = Self> {
+        let size = M::size();
+        std::iter::successors(Some(BigInt::zero()), |n| Some(n + 1u32))
+            .take_while(move |n| *n < size)
+            .map(|n| Self::from_index(&n))
+    }
 }
 
 impl<M: FiniteField> Display for FieldElement<M> {
@@ -77,7 +211,7 @@ impl<M: FiniteField> Add for FieldElement<M> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self {
-        Self::new(self.0 + rhs.0)
+        Self::reduced(self.0 + rhs.0)
     }
 }
 
@@ -87,7 +221,7 @@ impl<M: FiniteField> Neg for FieldElement<M> {
     type Output = Self;
 
     fn neg(self) -> Self {
-        Self::new(M::modulus() - self.0)
+        Self::reduced(M::modulus() - self.0)
     }
 }
 
@@ -108,7 +242,7 @@ impl<M: FiniteField> Mul for FieldElement<M> {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self {
-        Self::new(self.0 * rhs.0)
+        Self::reduced(self.0 * rhs.0)
     }
 }
 
@@ -117,6 +251,7 @@ impl<M: FiniteField> Mul for FieldElement<M> {
 impl<M: FiniteField> Div for FieldElement<M> {
     type Output = Self;
 
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, rhs: Self) -> Self {
         self * rhs.inverse()
     }
@@ -130,8 +265,11 @@ impl<M: FiniteField> Default for FieldElement<M> {
 
 #[cfg(test)]
 mod tests {
+    use num_bigint::BigInt;
+
     use crate::{
-        fields::{Fe13, Fe13_2, Ff13},
+        fields::{Fe13, Fe13_2, Fe13_4, Fe43_6, FeBn254, Ff13, Ff13_2, Ff13_4, Ff43_6, FfBn254},
+        finite_field::FiniteField,
         polynomial::Polynomial,
     };
 
@@ -262,4 +400,96 @@ mod tests {
         let res: Fe13_2 = Polynomial::from(vec![9]).into();
         assert_eq!(element.pow::<Ff13>(5), res);
     }
+
+    #[test]
+    fn test_field_size() {
+        assert_eq!(Ff13::size(), BigInt::from(13));
+        assert_eq!(Ff13_4::characteristic(), BigInt::from(13));
+        assert_eq!(Ff13_4::degree(), 4);
+        assert_eq!(Ff13_4::size(), BigInt::from(28561));
+        assert_eq!(Ff43_6::size(), BigInt::from(6321363049i64));
+        assert_eq!(FfBn254::degree(), 1);
+    }
+
+    #[test]
+    fn test_field_coefficients() {
+        let element: Fe13_2 = Polynomial::from(vec![7]).into();
+        let coefficients = Ff13_2::to_coefficients(element.value());
+        assert_eq!(coefficients, vec![BigInt::from(7), BigInt::from(0)]);
+        assert_eq!(Ff13_2::from_coefficients(&coefficients), *element.value());
+
+        let reduced = Ff13_2::from_coefficients(&[BigInt::from(-1), BigInt::from(27)]);
+        assert_eq!(reduced, Polynomial::from(vec![12, 1]));
+        assert_eq!(
+            Ff13::to_coefficients(Fe13::new(5).value()),
+            vec![BigInt::from(5)]
+        );
+    }
+
+    #[test]
+    fn test_pow_bigint() {
+        let a = Fe13::new(3);
+        assert_eq!(a.pow_bigint(&BigInt::from(5)), Fe13::new(9));
+        assert_eq!(a.pow_bigint(&BigInt::from(0)), Fe13::one());
+        assert_eq!(a.pow_bigint(&BigInt::from(-1)), a.inverse());
+
+        let b: Fe13_4 = Polynomial::from(vec![1, 2, 0, 5]).into();
+        assert_eq!(b.pow_bigint(&Ff13_4::size()), b);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        for i in 0..13 {
+            let a = Fe13::new(i);
+            match a.sqrt() {
+                Some(root) => assert_eq!(root.clone() * root, a),
+                None => assert!(!a.is_square()),
+            }
+        }
+        // the squares mod 13 are 0, 1, 3, 4, 9, 10, 12
+        assert_eq!((0..13).filter(|i| Fe13::new(*i).is_square()).count(), 7);
+
+        let elements: Vec<Fe13_2> = vec![
+            Polynomial::from(vec![7, 3]).into(),
+            Polynomial::from(vec![0, 5]).into(),
+            Polynomial::from(vec![2]).into(),
+        ];
+        for a in elements {
+            let square = a.clone() * a.clone();
+            let root = square.sqrt().unwrap();
+            assert!(root == a || root == -a);
+        }
+
+        let a: Fe43_6 = Polynomial::from(vec![1, 42, 0, 7, 0, 9]).into();
+        let root = (a.clone() * a.clone()).sqrt().unwrap();
+        assert!(root == a || root == -a);
+
+        let a = FeBn254::new(BigInt::from(123456789));
+        let root = (a.clone() * a.clone()).sqrt().unwrap();
+        assert!(root == a || root == -a);
+    }
+
+    #[test]
+    fn test_elements() {
+        let elements: Vec<Fe13_2> = Fe13_2::elements().collect();
+        assert_eq!(elements.len(), 169);
+        assert!(elements[0].is_zero());
+        assert_eq!(elements[1], Fe13_2::one());
+        assert_eq!(elements[14], Polynomial::from(vec![1, 1]).into());
+        for (i, a) in elements.iter().enumerate() {
+            assert!(elements[i + 1..].iter().all(|b| a != b));
+        }
+        assert!(!Fe13_2::non_square().is_square());
+    }
+
+    #[test]
+    fn test_sgn0() {
+        assert!(Fe13::new(3).sgn0());
+        assert!(!Fe13::new(4).sgn0());
+        assert!(!Fe13::zero().sgn0());
+        let a: Fe13_2 = Polynomial::from(vec![0, 5]).into();
+        assert!(a.sgn0());
+        let b: Fe13_2 = Polynomial::from(vec![2, 5]).into();
+        assert!(!b.sgn0());
+    }
 }
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{str::FromStr, sync::OnceLock};
 
 use derive_lib::polynomial_inverse;
 use num_bigint::BigInt;
@@ -157,7 +157,92 @@ impl FiniteField for Ff43_6 {
     fn inverse(value: &Self::T) -> Self::T;
 }
 
+// ---------------- Ff 103 ---------------------
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Ff103;
+
+impl FiniteField for Ff103 {
+    // the order of TinyBn over F_103^12 needs more than 64 bits
+    type T = i128;
+
+    fn modulus() -> Self::T {
+        103
+    }
+
+    fn one() -> Self::T {
+        1
+    }
+
+    fn zero() -> Self::T {
+        0
+    }
+}
+
+impl NonExtendedField for Ff103 {
+    fn to_bits(s: i128) -> Vec<bool> {
+        let max = i128::BITS - s.leading_zeros();
+        let mut res = vec![false; max as usize];
+        for i in 0..max {
+            res[i as usize] = (s >> (max - 1 - i) & 1) != 0;
+        }
+        res
+    }
+
+    fn to_uint(s: Self::T) -> Option<usize> {
+        Some(s.try_into().unwrap())
+    }
+
+    fn from_uint(s: usize) -> Option<Self::T> {
+        Some(s.try_into().unwrap())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Ff103_2;
+
+impl FiniteField for Ff103_2 {
+    type T = Polynomial<Fe103>;
+
+    fn modulus() -> Self::T {
+        // i^2 + 1
+        Polynomial::from(vec![1, 0, 1])
+    }
+
+    fn zero() -> Self::T {
+        Polynomial::new(vec![Fe103::zero()])
+    }
+
+    fn one() -> Self::T {
+        Polynomial::new(vec![Fe103::one()])
+    }
+
+    #[polynomial_inverse]
+    fn inverse(value: &Self::T) -> Self::T;
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Ff103_12;
+
+impl FiniteField for Ff103_12 {
+    type T = Polynomial<Fe103>;
+
+    fn modulus() -> Self::T {
+        // w^12 - 4 * w^6 + 5, so that w^6 = 2 + i with i = w^6 - 2
+        Polynomial::from(vec![5, 0, 0, 0, 0, 0, -4, 0, 0, 0, 0, 0, 1])
+    }
+
+    fn zero() -> Self::T {
+        Polynomial::new(vec![Fe103::zero()])
+    }
+
+    fn one() -> Self::T {
+        Polynomial::new(vec![Fe103::one()])
+    }
+
+    #[polynomial_inverse]
+    fn inverse(value: &Self::T) -> Self::T;
+}
 
 // ---------------- Ff Bn254 ---------------------
 
@@ -168,10 +253,16 @@ impl FiniteField for FfBn254 {
     type T = BigInt;
 
     fn modulus() -> Self::T {
-        BigInt::from_str(
-            "21888242871839275222246405745257275088696311157297823662689037894645226208583",
-        )
-        .unwrap()
+        // parsed once, since the modulus is needed for every reduction
+        static MODULUS: OnceLock<BigInt> = OnceLock::new();
+        MODULUS
+            .get_or_init(|| {
+                BigInt::from_str(
+                    "21888242871839275222246405745257275088696311157297823662689037894645226208583",
+                )
+                .unwrap()
+            })
+            .clone()
     }
 
     fn one() -> Self::T {
@@ -202,9 +293,64 @@ impl NonExtendedField for FfBn254 {
     }
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct FfBn254_2;
+
+impl FiniteField for FfBn254_2 {
+    type T = Polynomial<FeBn254>;
+
+    fn modulus() -> Self::T {
+        // i^2 + 1
+        Polynomial::from(vec![BigInt::one(), BigInt::zero(), BigInt::one()])
+    }
+
+    fn zero() -> Self::T {
+        Polynomial::new(vec![FeBn254::zero()])
+    }
+
+    fn one() -> Self::T {
+        Polynomial::new(vec![FeBn254::one()])
+    }
+
+    #[polynomial_inverse]
+    fn inverse(value: &Self::T) -> Self::T;
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct FfBn254_12;
+
+impl FiniteField for FfBn254_12 {
+    type T = Polynomial<FeBn254>;
+
+    fn modulus() -> Self::T {
+        // w^12 - 18 * w^6 + 82, so that w^6 = 9 + i with i = w^6 - 9
+        let mut coeffs = vec![BigInt::zero(); 13];
+        coeffs[0] = BigInt::from(82);
+        coeffs[6] = BigInt::from(-18);
+        coeffs[12] = BigInt::one();
+        Polynomial::from(coeffs)
+    }
+
+    fn zero() -> Self::T {
+        Polynomial::new(vec![FeBn254::zero()])
+    }
+
+    fn one() -> Self::T {
+        Polynomial::new(vec![FeBn254::one()])
+    }
+
+    #[polynomial_inverse]
+    fn inverse(value: &Self::T) -> Self::T;
+}
+
 pub type Fe13 = FieldElement<Ff13>;
 pub type Fe13_2 = FieldElement<Ff13_2>;
 pub type Fe13_4 = FieldElement<Ff13_4>;
 pub type Fe43 = FieldElement<Ff43>;
 pub type Fe43_6 = FieldElement<Ff43_6>;
+pub type Fe103 = FieldElement<Ff103>;
+pub type Fe103_2 = FieldElement<Ff103_2>;
+pub type Fe103_12 = FieldElement<Ff103_12>;
 pub type FeBn254 = FieldElement<FfBn254>;
+pub type FeBn254_2 = FieldElement<FfBn254_2>;
+pub type FeBn254_12 = FieldElement<FfBn254_12>;
@@ -1,12 +1,23 @@
 use std::{
+    any::{type_name, TypeId},
+    cell::RefCell,
+    collections::HashSet,
     fmt::Display,
     ops::{Add, BitAnd, Div, Mul, Neg, Rem, Shr, Sub},
-    usize,
+    sync::{OnceLock, RwLock},
 };
 
+use num_bigint::BigInt;
+use num_integer::Integer;
 use num_traits::Pow;
 
-pub trait FiniteField: Copy + Eq {
+use crate::{
+    field_element::FieldElement,
+    number_theory::is_prime,
+    polynomial::{is_irreducible, Polynomial},
+};
+
+pub trait FiniteField: Copy + Eq + 'static {
     type T: Clone
         + PartialEq
         + Default
@@ -16,7 +27,8 @@ pub trait FiniteField: Copy + Eq {
         + Sub<Output = Self::T>
         + Mul<Output = Self::T>
         + Div<Output = Self::T>
-        + Rem<Output = Self::T>;
+        + Rem<Output = Self::T>
+        + FieldValue;
 
     /// Returns the modulus of the field.
     fn modulus() -> Self::T;
@@ -57,14 +69,187 @@ pub trait FiniteField: Copy + Eq {
 
         Self::reduce(t0)
     }
+
+    /// Returns the characteristic p of the field.
+    fn characteristic() -> BigInt {
+        Self::T::field_size(&Self::modulus()).0
+    }
+
+    /// Returns the degree m of the field over its prime subfield.
+    fn degree() -> usize {
+        Self::T::field_size(&Self::modulus()).1
+    }
+
+    /// Checks that the modulus defines a field: a prime for prime fields,
+    /// and for extensions an irreducible polynomial over a valid field.
+    fn is_valid_modulus() -> bool {
+        Self::T::is_field_modulus(&Self::modulus())
+    }
+
+    /// Panics unless the modulus defines a field. The check runs the first
+    /// time an element of the field is built, and is skipped afterwards.
+    fn assert_valid_modulus() {
+        check_once::<Self>(|| {
+            assert!(
+                Self::is_valid_modulus(),
+                "the modulus of {} does not define a field",
+                type_name::<Self>()
+            )
+        });
+    }
+
+    /// Returns the number of elements p^m of the field.
+    fn size() -> BigInt {
+        Pow::pow(Self::characteristic(), Self::degree())
+    }
+
+    /// Returns the m coefficients of an element over the prime subfield,
+    /// each in [0, p), lowest degree first.
+    fn to_coefficients(value: &Self::T) -> Vec<BigInt> {
+        let p = Self::characteristic();
+        value
+            .to_coefficients(Self::degree())
+            .iter()
+            .map(|c| c.mod_floor(&p))
+            .collect()
+    }
+
+    /// Builds an element from its coefficients over the prime subfield,
+    /// reducing each of them modulo p.
+    fn from_coefficients(coefficients: &[BigInt]) -> Self::T {
+        let p = Self::characteristic();
+        let reduced: Vec<BigInt> = coefficients.iter().map(|c| c.mod_floor(&p)).collect();
+        Self::reduce(Self::T::from_coefficients(&reduced))
+    }
+}
+
+/// Runs `check` the first time it is reached for the type T, so that the
+/// parameters of a field or a curve are checked once and not on every
+/// operation.
+pub(crate) fn check_once<T: 'static>(check: impl FnOnce()) {
+    static CHECKED: OnceLock<RwLock<HashSet<TypeId>>> = OnceLock::new();
+    thread_local! {
+        // the types already seen by this thread, to keep the lock off the
+        // path of every field operation
+        static SEEN: RefCell<Vec<TypeId>> = const { RefCell::new(Vec::new()) };
+    }
+
+    let id = TypeId::of::<T>();
+    if SEEN.with(|seen| seen.borrow().contains(&id)) {
+        return;
+    }
+    let checked = CHECKED.get_or_init(Default::default);
+    if !checked.read().unwrap().contains(&id) {
+        // the lock is not held during the check, which builds elements of
+        // other fields
+        check();
+        checked.write().unwrap().insert(id);
+    }
+    SEEN.with(|seen| seen.borrow_mut().push(id));
+}
+
+/// Conversion between the values stored in a field and their coefficients
+/// over the prime subfield, which lets the field size and the coefficients
+/// be computed uniformly for prime fields and their extensions.
+pub trait FieldValue: Sized {
+    /// Returns the characteristic and the degree of the field with this modulus.
+    fn field_size(modulus: &Self) -> (BigInt, usize);
+
+    /// Checks that the modulus defines a field.
+    fn is_field_modulus(modulus: &Self) -> bool;
+
+    /// Returns the coefficients of the value, padded with zeros to `degree`.
+    fn to_coefficients(&self, degree: usize) -> Vec<BigInt>;
+
+    /// Builds a value from already reduced coefficients.
+    fn from_coefficients(coefficients: &[BigInt]) -> Self;
+}
+
+macro_rules! impl_integer_field_value {
+    ($($t:ty),*) => {
+        $(
+            impl FieldValue for $t {
+                fn field_size(modulus: &Self) -> (BigInt, usize) {
+                    (BigInt::from(modulus.clone()), 1)
+                }
+
+                fn is_field_modulus(modulus: &Self) -> bool {
+                    is_prime(&BigInt::from(modulus.clone()))
+                }
+
+                fn to_coefficients(&self, _degree: usize) -> Vec<BigInt> {
+                    vec![BigInt::from(self.clone())]
+                }
+
+                fn from_coefficients(coefficients: &[BigInt]) -> Self {
+                    coefficients
+                        .first()
+                        .map(|c| <$t>::try_from(c.clone()).expect("coefficient out of range"))
+                        .unwrap_or_default()
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_field_value!(i16, i32, i64, i128, BigInt);
+
+impl<M: FiniteField> FieldValue for Polynomial<FieldElement<M>> {
+    fn field_size(modulus: &Self) -> (BigInt, usize) {
+        (M::characteristic(), modulus.degree() * M::degree())
+    }
+
+    fn is_field_modulus(modulus: &Self) -> bool {
+        M::is_valid_modulus() && is_irreducible(modulus)
+    }
+
+    fn to_coefficients(&self, degree: usize) -> Vec<BigInt> {
+        let zero = FieldElement::<M>::zero();
+        (0..degree / M::degree())
+            .flat_map(|i| {
+                let c = self.coefficients().get(i).unwrap_or(&zero);
+                M::to_coefficients(c.value())
+            })
+            .collect()
+    }
+
+    fn from_coefficients(coefficients: &[BigInt]) -> Self {
+        let mut coeffs: Vec<_> = coefficients
+            .chunks(M::degree())
+            .map(|chunk| FieldElement::new(M::from_coefficients(chunk)))
+            .collect();
+        if coeffs.is_empty() {
+            coeffs.push(FieldElement::zero());
+        }
+        Polynomial::new(coeffs)
+    }
 }
 
 pub trait NonExtendedField:
-    FiniteField<T: Shr<usize, Output = Self::T> + BitAnd + Pow<usize, Output = Self::T>>
+    FiniteField<
+    T: Shr<usize, Output = Self::T>
+           + BitAnd
+           + Pow<usize, Output = Self::T>
+           + Into<BigInt>
+           + TryFrom<BigInt>,
+>
 {
     fn to_bits(s: Self::T) -> Vec<bool>;
 
     fn to_uint(s: Self::T) -> Option<usize>;
 
     fn from_uint(s: usize) -> Option<Self::T>;
+
+    fn to_bigint(s: &Self::T) -> BigInt {
+        s.clone().into()
+    }
+
+    fn from_bigint(s: &BigInt) -> Option<Self::T> {
+        Self::T::try_from(s.clone()).ok()
+    }
+
+    /// The integers modulo n form a field only when n is prime.
+    fn is_prime_modulus() -> bool {
+        is_prime(&Self::to_bigint(&Self::modulus()))
+    }
 }
@@ -0,0 +1,235 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+use crate::{
+    elliptic_curve::{AffinePoint, EllipticCurve},
+    field_element::FieldElement,
+    finite_field::{FiniteField, NonExtendedField},
+    pairing::Pairing,
+    projective::JacobianPoint,
+};
+
+// The GLV method.
+//
+// On a curve y^2 = x^3 + b, the map φ(x, y) = (βx, y), β being a primitive
+// cube root of unity, is an endomorphism that costs a single multiplication.
+// On G1 it acts as multiplication by a root λ of λ^2 + λ + 1 mod r, so
+//
+//     k * P = k1 * P + k2 * φ(P)   whenever k = k1 + k2 * λ mod r.
+//
+// Short k1 and k2 of about √r are found by reducing k against a short basis
+// of the lattice {(a, b) : a + b * λ = 0 mod r}, obtained with the extended
+// Euclidean algorithm. Both halves are then processed at once with Shamir's
+// trick, halving the number of doublings.
+
+pub trait GlvCurve: EllipticCurve {
+    /// A primitive cube root of unity in the field the curve is defined over.
+    fn beta() -> FieldElement<Self::BaseField>;
+
+    /// The eigenvalue of the endomorphism on G1, a root of λ^2 + λ + 1 mod r.
+    fn lambda() -> BigInt;
+
+    /// Two short vectors (a, b) of the lattice of the pairs with a + b * λ = 0
+    /// mod r. The remainders r_i = s_i * r + t_i * λ of the extended Euclidean
+    /// algorithm on r and λ give vectors (r_i, -t_i) of the lattice; the short
+    /// ones are found where r_i drops below √r.
+    fn glv_basis() -> [(BigInt, BigInt); 2] {
+        let r = Self::ScalarField::to_bigint(&Self::r());
+        let (mut r0, mut r1) = (r.clone(), Self::lambda().mod_floor(&r));
+        let (mut t0, mut t1) = (BigInt::zero(), BigInt::one());
+
+        while &r1 * &r1 >= r {
+            let quotient = &r0 / &r1;
+            let r2 = &r0 - &quotient * &r1;
+            r0 = std::mem::replace(&mut r1, r2);
+            let t2 = &t0 - &quotient * &t1;
+            t0 = std::mem::replace(&mut t1, t2);
+        }
+
+        // (r0, t0) is the last remainder above √r and (r1, t1) the first below
+        let quotient = &r0 / &r1;
+        let r2 = &r0 - &quotient * &r1;
+        let t2 = &t0 - &quotient * &t1;
+
+        let v1 = (r1, -t1);
+        let norm = |v: &(BigInt, BigInt)| &v.0 * &v.0 + &v.1 * &v.1;
+        let (u, w) = ((r0, -t0), (r2, -t2));
+        let v2 = if norm(&u) <= norm(&w) { u } else { w };
+        [v1, v2]
+    }
+
+    /// Splits k into (k1, k2) with k = k1 + k2 * λ mod r, where both halves
+    /// are about √r: (k, 0) is written in the basis (v1, v2), the coefficients
+    /// are rounded, and the difference is a short vector.
+    fn glv_decompose(k: &BigInt) -> (BigInt, BigInt) {
+        let [(a1, b1), (a2, b2)] = Self::glv_basis();
+        let det = &a1 * &b2 - &a2 * &b1;
+
+        let c1 = round_div(&(&b2 * k), &det);
+        let c2 = round_div(&(-&b1 * k), &det);
+        let k1 = k - &c1 * &a1 - &c2 * &a2;
+        let k2 = -&c1 * &b1 - &c2 * &b2;
+        (k1, k2)
+    }
+}
+
+/// The integer closest to a / b.
+fn round_div(a: &BigInt, b: &BigInt) -> BigInt {
+    let (a, b) = if b.is_negative() {
+        (-a, -b)
+    } else {
+        (a.clone(), b.clone())
+    };
+    let numerator: BigInt = a * 2 + &b;
+    numerator.div_floor(&(b * 2))
+}
+
+/// Computes k1 * P + k2 * Q with Shamir's trick: a single chain of
+/// doublings, adding P, Q or P + Q depending on the bits of k1 and k2.
+pub fn mul_double_scalar<E: EllipticCurve>(
+    p: &AffinePoint<E>,
+    k1: &BigInt,
+    q: &AffinePoint<E>,
+    k2: &BigInt,
+) -> AffinePoint<E> {
+    let p = if k1.is_negative() {
+        -p.clone()
+    } else {
+        p.clone()
+    };
+    let q = if k2.is_negative() {
+        -q.clone()
+    } else {
+        q.clone()
+    };
+    let (k1, k2) = (k1.magnitude(), k2.magnitude());
+    let sum = p.clone() + q.clone();
+
+    let mut acc = JacobianPoint::<E>::infinity();
+    for i in (0..k1.bits().max(k2.bits())).rev() {
+        acc = acc.double();
+        match (k1.bit(i), k2.bit(i)) {
+            (true, true) => acc = acc.add_mixed(&sum),
+            (true, false) => acc = acc.add_mixed(&p),
+            (false, true) => acc = acc.add_mixed(&q),
+            (false, false) => {}
+        }
+    }
+    acc.to_affine()
+}
+
+impl<E: GlvCurve> AffinePoint<E> {
+    /// The endomorphism φ(x, y) = (βx, y).
+    pub fn endomorphism(&self) -> Self {
+        match self {
+            AffinePoint::XY(x, y) => AffinePoint::new_unchecked(E::beta() * x.clone(), y.clone()),
+            AffinePoint::Infinity => AffinePoint::Infinity,
+        }
+    }
+
+    /// GLV scalar multiplication k * P = k1 * P + k2 * φ(P).
+    /// Only valid for points of G1, where φ acts as λ.
+    ///
+    /// The `Mul` operator is not routed here: it is implemented once for all
+    /// curves, and on a `GlvCurve` it is also used on G2 and the rest of E[r],
+    /// where φ does not act as λ. Stable Rust cannot specialize it for G1.
+    pub fn mul_glv(&self, scalar: <E::ScalarField as FiniteField>::T) -> Self {
+        let r = E::ScalarField::to_bigint(&E::r());
+        debug_assert!(
+            E::is_valid_g1_frobenius(self) && self.mul_double_and_add(&r).is_inf(),
+            "the point is not in G1"
+        );
+        let k = E::ScalarField::to_bigint(&scalar).mod_floor(&r);
+        let (k1, k2) = E::glv_decompose(&k);
+        mul_double_scalar(self, &k1, &self.endomorphism(), &k2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{curves::MoonMath, polynomial::Polynomial};
+
+    use super::*;
+
+    fn moonmath_g1() -> AffinePoint<MoonMath> {
+        AffinePoint::try_new_g1(
+            Polynomial::from(vec![27]).into(),
+            Polynomial::from(vec![34]).into(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_glv_parameters() {
+        let beta = MoonMath::beta();
+        assert_ne!(beta, FieldElement::one());
+        assert_eq!(beta.clone() * beta.clone() * beta, FieldElement::one());
+
+        let l = MoonMath::lambda();
+        let value: BigInt = &l * &l + &l + 1;
+        assert!((value % 13u32).is_zero());
+
+        let p = moonmath_g1();
+        assert_eq!(p.endomorphism(), p.mul_double_and_add(&l));
+        // on G2 the endomorphism acts as the other root λ^2
+        let q = MoonMath::generator();
+        assert_eq!(q.endomorphism(), q.mul_double_and_add(&(&l * &l)));
+    }
+
+    #[test]
+    fn test_glv_decompose() {
+        let r = BigInt::from(13);
+        for v in MoonMath::glv_basis() {
+            assert!(((&v.0 + &v.1 * MoonMath::lambda()) % &r).is_zero());
+        }
+        for k in 0..40 {
+            let k = BigInt::from(k);
+            let (k1, k2) = MoonMath::glv_decompose(&k);
+            assert_eq!(
+                (&k1 + &k2 * MoonMath::lambda() - &k).mod_floor(&r),
+                BigInt::zero()
+            );
+            assert!(k1.abs() < BigInt::from(8) && k2.abs() < BigInt::from(8));
+        }
+    }
+
+    #[test]
+    fn test_glv_mul() {
+        let p = moonmath_g1();
+        for k in [0, 1, 2, 5, 12, 13, 14, 100, 6321251663] {
+            assert_eq!(p.mul_glv(k), p.mul_double_and_add(&BigInt::from(k)));
+        }
+        assert_eq!(
+            AffinePoint::<MoonMath>::Infinity.mul_glv(7),
+            AffinePoint::Infinity
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the point is not in G1")]
+    fn test_glv_mul_outside_g1() {
+        // the generator lies in G2, where φ acts as λ^2
+        MoonMath::generator().mul_glv(5);
+    }
+
+    #[test]
+    fn test_glv_mul_double_scalar() {
+        let p = moonmath_g1();
+        let q = MoonMath::generator();
+        for (k1, k2) in [(0, 0), (3, 0), (0, 5), (7, 11), (-4, 9), (12, -12)] {
+            let (k1, k2) = (BigInt::from(k1), BigInt::from(k2));
+            assert_eq!(
+                mul_double_scalar(&p, &k1, &q, &k2),
+                p.mul_double_and_add(&k1) + q.mul_double_and_add(&k2)
+            );
+        }
+        // P + Q is the point at infinity
+        let k = BigInt::from(3);
+        assert_eq!(
+            mul_double_scalar(&p, &k, &-p.clone(), &k),
+            AffinePoint::Infinity
+        );
+    }
+}
@@ -0,0 +1,600 @@
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Zero};
+
+use crate::{
+    elliptic_curve::{AffinePoint, EllipticCurve},
+    field_element::FieldElement,
+    finite_field::{FiniteField, NonExtendedField},
+    isogeny::IsogenyMap,
+    point_counting::{definition_field_order, field_of_definition},
+    polynomial::Polynomial,
+    schoof::has_root,
+    sha256::{sha256, BLOCK_SIZE, DIGEST_SIZE},
+    twist::Twist,
+};
+
+// Hashing to curves, following RFC 9380.
+//
+// A message is first hashed to elements u of the field F_q0 the curve is
+// defined over: expand_message_xmd stretches it with SHA-256 into uniform
+// bytes, which are read as integers modulo p with k = 128 extra bits so that
+// the bias is negligible. Each u is then sent to a point by a deterministic
+// map:
+//
+// - the simplified SWU map, for curves with ab ≠ 0. Curves with a = 0 or
+//   b = 0 can still use it on an isogenous curve with ab ≠ 0, followed by
+//   the isogeny.
+// - the Shallue-van de Woestijne map, which works for every curve.
+//
+// Both maps only hit part of the curve, so hash_to_curve adds the images of
+// two field elements, which makes the result indistinguishable from a random
+// point. Multiplying by the cofactor h_eff finally lands in the subgroup of
+// order r: G1 for the curve itself, and G2 once a point of the twist is
+// untwisted.
+
+/// The security level in bits, fixing how many bytes are hashed per element.
+const SECURITY_BITS: u64 = 128;
+
+/// Produces `len_in_bytes` uniform bytes from a message and a domain
+/// separation tag with SHA-256, as in section 5.3.1 of RFC 9380.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+    // tags longer than 255 bytes are replaced by their hash
+    let mut dst_prime = if dst.len() > 255 {
+        sha256(&[b"H2C-OVERSIZE-DST-", dst].concat()).to_vec()
+    } else {
+        dst.to_vec()
+    };
+    dst_prime.push(dst_prime.len() as u8);
+
+    let ell = len_in_bytes.div_ceil(DIGEST_SIZE);
+    assert!(
+        ell <= 255 && len_in_bytes <= 65535,
+        "too many bytes requested"
+    );
+
+    let mut input = vec![0u8; BLOCK_SIZE];
+    input.extend_from_slice(msg);
+    input.extend_from_slice(&(len_in_bytes as u16).to_be_bytes());
+    input.push(0);
+    input.extend_from_slice(&dst_prime);
+    let b0 = sha256(&input);
+
+    let mut b = sha256(&[&b0[..], &[1], &dst_prime].concat());
+    let mut uniform = b.to_vec();
+    for i in 2..=ell {
+        let mixed: Vec<u8> = b0.iter().zip(b).map(|(x, y)| x ^ y).collect();
+        b = sha256(&[&mixed[..], &[i as u8], &dst_prime].concat());
+        uniform.extend_from_slice(&b);
+    }
+    uniform.truncate(len_in_bytes);
+    uniform
+}
+
+/// Hashes a message to `count` elements of M, each built from `degree`
+/// coefficients over the prime subfield: 1 for elements of F_p, the degree
+/// of M for elements of the whole field.
+pub fn hash_to_field<M: FiniteField>(
+    msg: &[u8],
+    dst: &[u8],
+    count: usize,
+    degree: usize,
+) -> Vec<FieldElement<M>> {
+    let length = (M::characteristic().bits() + SECURITY_BITS).div_ceil(8) as usize;
+    let uniform = expand_message_xmd(msg, dst, count * degree * length);
+    uniform
+        .chunks(degree * length)
+        .map(|element| {
+            let coefficients: Vec<BigInt> = element
+                .chunks(length)
+                .map(|bytes| BigInt::from_bytes_be(Sign::Plus, bytes))
+                .collect();
+            FieldElement::new(M::from_coefficients(&coefficients))
+        })
+        .collect()
+}
+
+fn constant<M: FiniteField>(n: i64) -> FieldElement<M> {
+    FieldElement::new(M::from_coefficients(&[BigInt::from(n)]))
+}
+
+/// The right-hand side x^3 + ax + b of the curve equation.
+fn g<E: EllipticCurve>(x: &FieldElement<E::BaseField>) -> FieldElement<E::BaseField> {
+    x.clone() * x.clone() * x.clone() + E::a() * x.clone() + E::b()
+}
+
+/// The inverse of x, or 0 for x = 0.
+fn inv0<M: FiniteField>(x: &FieldElement<M>) -> FieldElement<M> {
+    if x.is_zero() {
+        FieldElement::zero()
+    } else {
+        x.inverse()
+    }
+}
+
+/// Whether the maps work in the prime subfield of the base field rather than
+/// in the whole field, i.e. whether the curve is defined over F_p.
+fn over_prime_subfield<E: EllipticCurve>() -> bool {
+    field_of_definition::<E>().1 > 1
+}
+
+fn to_prime_field<E: EllipticCurve>(
+    x: &FieldElement<E::BaseField>,
+) -> FieldElement<E::ScalarField> {
+    let c = &E::BaseField::to_coefficients(x.value())[0];
+    FieldElement::new(E::ScalarField::from_bigint(c).unwrap())
+}
+
+/// Whether x is a square of the field of definition. Every element of F_p is
+/// a square in an extension of even degree, so the test happens in F_p.
+fn is_square<E: EllipticCurve>(x: &FieldElement<E::BaseField>) -> bool {
+    if over_prime_subfield::<E>() {
+        to_prime_field::<E>(x).is_square()
+    } else {
+        x.is_square()
+    }
+}
+
+/// A square root in the field of definition.
+fn sqrt<E: EllipticCurve>(x: &FieldElement<E::BaseField>) -> FieldElement<E::BaseField> {
+    let root = if over_prime_subfield::<E>() {
+        to_prime_field::<E>(x).sqrt().map(|root| {
+            let c = E::ScalarField::to_bigint(root.value());
+            FieldElement::new(E::BaseField::from_coefficients(&[c]))
+        })
+    } else {
+        x.sqrt()
+    };
+    root.expect("the map only takes square roots of squares")
+}
+
+/// The candidates for Z in the order of RFC 9380 appendix H: 1, -1, 2, -2, ...
+/// Over an extension field, n + X and -(n + X) follow each n, X being the
+/// generator of the extension.
+fn z_candidates<E: EllipticCurve>() -> impl Iterator<Item = FieldElement<E::BaseField>> {
+    let x: FieldElement<E::BaseField> = FieldElement::new(E::BaseField::from_coefficients(&[
+        BigInt::zero(),
+        BigInt::one(),
+    ]));
+    let extension = !over_prime_subfield::<E>() && E::BaseField::degree() > 1;
+    (1..).flat_map(move |n| {
+        let mut candidates = vec![constant(n), constant(-n)];
+        if extension {
+            candidates.push(constant::<E::BaseField>(n) + x.clone());
+            candidates.push(-(constant::<E::BaseField>(n) + x.clone()));
+        }
+        candidates
+    })
+}
+
+/// The first Z suitable for the simplified SWU map: a non-square other
+/// than -1, such that g(x) - Z is irreducible and g(B / (Z * A)) is a square.
+pub fn find_z_sswu<E: EllipticCurve>() -> FieldElement<E::BaseField> {
+    let (a, b) = (E::a(), E::b());
+    assert!(
+        !a.is_zero() && !b.is_zero(),
+        "the simplified SWU map needs ab ≠ 0"
+    );
+    let q = field_of_definition::<E>().0;
+    z_candidates::<E>()
+        .find(|z| {
+            let cubic = Polynomial::new(vec![
+                b.clone() - z.clone(),
+                a.clone(),
+                FieldElement::zero(),
+                FieldElement::one(),
+            ]);
+            !is_square::<E>(z)
+                && *z != constant(-1)
+                && !has_root(&cubic, &q)
+                && is_square::<E>(&g::<E>(&(b.clone() / (z.clone() * a.clone()))))
+        })
+        .unwrap()
+}
+
+/// The first Z suitable for the Shallue-van de Woestijne map: g(Z) ≠ 0,
+/// -(3Z^2 + 4A) / (4g(Z)) is a non-zero square, and g(Z) or g(-Z / 2) is a
+/// square.
+pub fn find_z_svdw<E: EllipticCurve>() -> FieldElement<E::BaseField> {
+    z_candidates::<E>()
+        .find(|z| {
+            let gz = g::<E>(z);
+            let h = constant(3) * z.clone() * z.clone() + constant(4) * E::a();
+            if gz.is_zero() || h.is_zero() {
+                return false;
+            }
+            is_square::<E>(&(-h / (constant(4) * gz.clone())))
+                && (is_square::<E>(&gz) || is_square::<E>(&g::<E>(&(-z.clone() / constant(2)))))
+        })
+        .unwrap()
+}
+
+/// Fixes the sign of y so that it matches the sign of u.
+fn with_sign_of<M: FiniteField>(y: FieldElement<M>, u: &FieldElement<M>) -> FieldElement<M> {
+    if y.sgn0() == u.sgn0() {
+        y
+    } else {
+        -y
+    }
+}
+
+/// The simplified SWU map of section 6.6.2, for curves with ab ≠ 0.
+pub fn map_to_curve_sswu<E: EllipticCurve>(
+    u: &FieldElement<E::BaseField>,
+    z: &FieldElement<E::BaseField>,
+) -> AffinePoint<E> {
+    let (a, b) = (E::a(), E::b());
+    let zu2 = z.clone() * u.clone() * u.clone();
+    let tv1 = inv0(&(zu2.clone() * zu2.clone() + zu2.clone()));
+    let x1 = if tv1.is_zero() {
+        b.clone() / (z.clone() * a.clone())
+    } else {
+        -b / a * (FieldElement::one() + tv1)
+    };
+
+    let gx1 = g::<E>(&x1);
+    let (x, y) = if is_square::<E>(&gx1) {
+        (x1, sqrt::<E>(&gx1))
+    } else {
+        let x2 = zu2 * x1;
+        let y2 = sqrt::<E>(&g::<E>(&x2));
+        (x2, y2)
+    };
+    AffinePoint::new_unchecked(x, with_sign_of(y, u))
+}
+
+/// The Shallue-van de Woestijne map of section 6.6.1, for any curve.
+pub fn map_to_curve_svdw<E: EllipticCurve>(
+    u: &FieldElement<E::BaseField>,
+    z: &FieldElement<E::BaseField>,
+) -> AffinePoint<E> {
+    let one = FieldElement::<E::BaseField>::one();
+    let gz = g::<E>(z);
+    let h = constant(3) * z.clone() * z.clone() + constant(4) * E::a();
+    let c2 = -z.clone() / constant(2);
+    let c3 = sqrt::<E>(&(-gz.clone() * h.clone()));
+    let c3 = if c3.sgn0() { -c3 } else { c3 };
+    let c4 = constant(-4) * gz.clone() / h;
+
+    let tv1 = u.clone() * u.clone() * gz;
+    let tv2 = one.clone() + tv1.clone();
+    let tv1 = one - tv1;
+    let tv3 = inv0(&(tv1.clone() * tv2.clone()));
+    let tv4 = u.clone() * tv1 * tv3.clone() * c3;
+
+    let x1 = c2.clone() - tv4.clone();
+    let x2 = c2 + tv4;
+    let x = if is_square::<E>(&g::<E>(&x1)) {
+        x1
+    } else if is_square::<E>(&g::<E>(&x2)) {
+        x2
+    } else {
+        let x3 = tv2.clone() * tv2 * tv3;
+        x3.clone() * x3 * c4 + z.clone()
+    };
+    let y = sqrt::<E>(&g::<E>(&x));
+    AffinePoint::new_unchecked(x, with_sign_of(y, u))
+}
+
+/// The simplified SWU map for a curve E with ab = 0: u is mapped to an
+/// isogenous curve I with ab ≠ 0, then sent to E by the isogeny.
+pub fn map_to_curve_sswu_isogeny<E, I>(
+    u: &FieldElement<E::BaseField>,
+    z: &FieldElement<E::BaseField>,
+    isogeny: &IsogenyMap<E::BaseField>,
+) -> AffinePoint<E>
+where
+    E: EllipticCurve,
+    I: EllipticCurve<BaseField = E::BaseField>,
+{
+    isogeny.apply(&map_to_curve_sswu::<I>(u, z))
+}
+
+/// Hashes a message to `count` elements of the field the curve is defined over.
+fn hash_to_definition_field<E: EllipticCurve>(
+    msg: &[u8],
+    dst: &[u8],
+    count: usize,
+) -> Vec<FieldElement<E::BaseField>> {
+    let degree = if over_prime_subfield::<E>() {
+        1
+    } else {
+        E::BaseField::degree()
+    };
+    hash_to_field(msg, dst, count, degree)
+}
+
+pub trait HashToCurve: EllipticCurve {
+    /// The constant Z of the map, found with the searches of RFC 9380
+    /// appendix H unless overridden.
+    fn z() -> FieldElement<Self::BaseField> {
+        if Self::a().is_zero() || Self::b().is_zero() {
+            find_z_svdw::<Self>()
+        } else {
+            find_z_sswu::<Self>()
+        }
+    }
+
+    /// Maps a field element to the curve: with the simplified SWU map when
+    /// ab ≠ 0, and with the Shallue-van de Woestijne map otherwise.
+    fn map_to_curve(u: &FieldElement<Self::BaseField>) -> AffinePoint<Self> {
+        if Self::a().is_zero() || Self::b().is_zero() {
+            map_to_curve_svdw(u, &Self::z())
+        } else {
+            map_to_curve_sswu(u, &Self::z())
+        }
+    }
+
+    /// The scalar clearing the cofactor, #E(F_q0) / r unless overridden.
+    fn h_eff() -> BigInt {
+        definition_field_order::<Self>() / Self::ScalarField::to_bigint(&Self::r())
+    }
+
+    /// Maps a point of the curve into the subgroup of order r, by
+    /// multiplying it by h_eff unless overridden with a faster map.
+    fn clear_cofactor(p: &AffinePoint<Self>) -> AffinePoint<Self> {
+        p.mul_double_and_add(&Self::h_eff())
+    }
+
+    /// The nonuniform encoding: a single field element is mapped to the curve.
+    fn encode_to_curve(msg: &[u8], dst: &[u8]) -> AffinePoint<Self> {
+        let u = hash_to_definition_field::<Self>(msg, dst, 1);
+        Self::clear_cofactor(&Self::map_to_curve(&u[0]))
+    }
+
+    /// The random oracle encoding: the images of two field elements are added.
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> AffinePoint<Self> {
+        let u = hash_to_definition_field::<Self>(msg, dst, 2);
+        Self::clear_cofactor(&(Self::map_to_curve(&u[0]) + Self::map_to_curve(&u[1])))
+    }
+
+    /// Hashes to G1, the points of order r of E(F_q0).
+    fn hash_to_g1(msg: &[u8], dst: &[u8]) -> AffinePoint<Self> {
+        Self::hash_to_curve(msg, dst)
+    }
+}
+
+/// Hashing to G2 by hashing to the twist and untwisting, available for every
+/// twist that can be hashed to.
+pub trait HashToG2: Twist<TwistedCurve: HashToCurve> {
+    /// Encodes to G2 with `encode_to_curve` on the twist.
+    fn encode_to_g2(msg: &[u8], dst: &[u8]) -> AffinePoint<Self> {
+        Self::untwist(&Self::TwistedCurve::encode_to_curve(msg, dst))
+    }
+
+    /// Hashes to G2 with `hash_to_curve` on the twist.
+    fn hash_to_g2(msg: &[u8], dst: &[u8]) -> AffinePoint<Self> {
+        Self::untwist(&Self::TwistedCurve::hash_to_curve(msg, dst))
+    }
+}
+
+impl<E: Twist<TwistedCurve: HashToCurve>> HashToG2 for E {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{MoonMath, MoonMathTwist, TinyJJ, TinyJJTwist},
+        elliptic_curve::points,
+        fields::{Fe13_4, Fe43, Ff13, Ff13_2, Ff13_4, Ff43},
+        isogeny::Isogeny,
+        pairing::Pairing,
+    };
+
+    use super::*;
+
+    const DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHA256-128";
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// y^2 = x^3 + 8 over F_43, with 52 points.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Curve8;
+
+    impl EllipticCurve for Curve8 {
+        type BaseField = Ff43;
+        type ScalarField = Ff43;
+
+        fn a() -> Fe43 {
+            Fe43::new(0)
+        }
+
+        fn b() -> Fe43 {
+            Fe43::new(8)
+        }
+
+        fn generator() -> AffinePoint<Self> {
+            AffinePoint::try_new(Fe43::new(2), Fe43::new(4)).unwrap()
+        }
+    }
+
+    /// y^2 = x^3 + 7x + 35 over F_43, which is 2-isogenous to Curve8
+    /// through the kernel {∞, (1, 0)}.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Curve8Iso;
+
+    impl EllipticCurve for Curve8Iso {
+        type BaseField = Ff43;
+        type ScalarField = Ff43;
+
+        fn a() -> Fe43 {
+            Fe43::new(7)
+        }
+
+        fn b() -> Fe43 {
+            Fe43::new(35)
+        }
+
+        fn generator() -> AffinePoint<Self> {
+            AffinePoint::try_new(Fe43::new(1), Fe43::new(0)).unwrap()
+        }
+    }
+
+    /// Vélu's formulas: x -> x + t / (x - 1), y -> y (1 - t / (x - 1)^2)
+    /// with t = 10.
+    fn curve8_isogeny() -> IsogenyMap<Ff43> {
+        let poly = |c: &[i64]| c.iter().map(|&c| Fe43::new(c)).collect();
+        IsogenyMap {
+            x_num: poly(&[10, 42, 1]),
+            x_den: poly(&[42, 1]),
+            y_num: poly(&[34, 41, 1]),
+            y_den: poly(&[1, 41, 1]),
+        }
+    }
+
+    impl HashToCurve for Curve8 {
+        fn z() -> Fe43 {
+            find_z_sswu::<Curve8Iso>()
+        }
+
+        fn map_to_curve(u: &Fe43) -> AffinePoint<Self> {
+            map_to_curve_sswu_isogeny::<Self, Curve8Iso>(u, &Self::z(), &curve8_isogeny())
+        }
+    }
+
+    #[test]
+    fn test_expand_message_xmd() {
+        // RFC 9380, appendix K.1
+        let expected = [
+            (
+                b"".as_slice(),
+                "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+            ),
+            (
+                b"abc".as_slice(),
+                "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+            ),
+            (
+                b"abcdef0123456789".as_slice(),
+                "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+            ),
+        ];
+        for (msg, uniform) in expected {
+            assert_eq!(hex(&expand_message_xmd(msg, DST, 0x20)), uniform);
+        }
+        assert_eq!(
+            hex(&expand_message_xmd(b"", DST, 0x80)),
+            "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbe\
+             e0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18\
+             eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dc\
+             c541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced"
+        );
+    }
+
+    #[test]
+    fn test_hash_to_field() {
+        let u = hash_to_field::<Ff13>(b"abc", DST, 3, 1);
+        assert_eq!(u.len(), 3);
+        assert_eq!(u, hash_to_field::<Ff13>(b"abc", DST, 3, 1));
+        assert_ne!(u, hash_to_field::<Ff13>(b"abd", DST, 3, 1));
+
+        // elements of the prime subfield of F_13^4 and of the whole field
+        let u = hash_to_field::<Ff13_4>(b"abc", DST, 2, 1);
+        assert!(u.iter().all(|u| u.is_in_prime_subfield()));
+        let u = hash_to_field::<Ff13_4>(b"abc", DST, 2, 4);
+        assert!(u.iter().any(|u| !u.is_in_prime_subfield()));
+    }
+
+    #[test]
+    fn test_find_z() {
+        let z = find_z_sswu::<TinyJJ>();
+        assert!(z.is_in_prime_subfield());
+        assert!(!to_prime_field::<TinyJJ>(&z).is_square());
+        assert_ne!(z, constant(-1));
+
+        // 1 and -1 fail since -3 / (4g(±1)) is not a square mod 43
+        assert_eq!(find_z_svdw::<MoonMath>(), constant(2));
+        assert!(!find_z_sswu::<TinyJJTwist>().is_square());
+    }
+
+    #[test]
+    fn test_maps_land_on_curve() {
+        let z = TinyJJ::z();
+        for n in 0..13 {
+            let u: Fe13_4 = Polynomial::from(vec![n]).into();
+            let point = map_to_curve_sswu::<TinyJJ>(&u, &z);
+            let (x, y) = point.xy().unwrap();
+            assert!(AffinePoint::<TinyJJ>::is_on_curve(&x, &y));
+            assert!(x.is_in_prime_subfield() && y.is_in_prime_subfield());
+            assert_eq!(y.sgn0(), u.sgn0());
+        }
+
+        let (z, z_iso) = (MoonMathTwist::z(), Curve8::z());
+        for u in FieldElement::<Ff43>::elements() {
+            let point = map_to_curve_svdw::<MoonMathTwist>(&u, &z);
+            let (x, y) = point.xy().unwrap();
+            assert!(AffinePoint::<MoonMathTwist>::is_on_curve(&x, &y));
+            assert_eq!(y.sgn0(), u.sgn0());
+
+            let point =
+                map_to_curve_sswu_isogeny::<Curve8, Curve8Iso>(&u, &z_iso, &curve8_isogeny());
+            if let Some((x, y)) = point.xy() {
+                assert!(AffinePoint::<Curve8>::is_on_curve(&x, &y));
+            }
+        }
+
+        let z = TinyJJTwist::z();
+        for u in FieldElement::<Ff13_2>::elements() {
+            let (x, y) = map_to_curve_sswu::<TinyJJTwist>(&u, &z).xy().unwrap();
+            assert!(AffinePoint::<TinyJJTwist>::is_on_curve(&x, &y));
+            assert!(y.is_zero() || y.sgn0() == u.sgn0());
+        }
+    }
+
+    #[test]
+    fn test_isogeny_map() {
+        let isogeny = curve8_isogeny();
+        // the kernel goes to infinity
+        let kernel = Curve8Iso::generator();
+        assert_eq!(isogeny.apply::<_, Curve8>(&kernel), AffinePoint::Infinity);
+
+        let p = AffinePoint::<Curve8Iso>::try_new(Fe43::new(2), Fe43::new(10)).unwrap();
+        let q = p.double();
+        assert_eq!(
+            isogeny.apply::<_, Curve8>(&(p.clone() + q.clone())),
+            isogeny.apply::<_, Curve8>(&p) + isogeny.apply::<_, Curve8>(&q)
+        );
+
+        // Vélu's formulas give the same map
+        let velu = Isogeny::from_kernel(&kernel);
+        assert!(velu.is_codomain::<Curve8>());
+        for p in points::<Curve8Iso>() {
+            assert_eq!(velu.evaluate::<Curve8>(&p), isogeny.apply::<_, Curve8>(&p));
+        }
+    }
+
+    #[test]
+    fn test_hash_to_g1() {
+        for msg in [b"".as_slice(), b"abc", b"abcdef0123456789"] {
+            let p = TinyJJ::hash_to_g1(msg, DST);
+            assert!(TinyJJ::is_valid_g1(&p));
+            assert_eq!(p, TinyJJ::hash_to_g1(msg, DST));
+
+            let p = MoonMath::hash_to_g1(msg, DST);
+            assert!(MoonMath::is_valid_g1(&p));
+
+            let p = TinyJJ::encode_to_curve(msg, DST);
+            assert!(TinyJJ::is_valid_g1(&p));
+
+            let p = Curve8::hash_to_curve(msg, DST);
+            assert!(p.mul_double_and_add(&BigInt::from(13)).is_inf());
+        }
+        assert_eq!(TinyJJ::h_eff(), BigInt::from(4));
+        assert_eq!(MoonMath::h_eff(), BigInt::from(3));
+    }
+
+    #[test]
+    fn test_hash_to_g2() {
+        for msg in [b"".as_slice(), b"abc", b"abcdef0123456789"] {
+            let q = TinyJJ::hash_to_g2(msg, DST);
+            assert!(TinyJJ::is_valid_g2(&q));
+
+            let q = MoonMath::hash_to_g2(msg, DST);
+            assert!(MoonMath::is_valid_g2(&q));
+
+            let q = MoonMath::encode_to_g2(msg, DST);
+            assert!(MoonMath::is_valid_g2(&q));
+        }
+        assert_eq!(TinyJJTwist::h_eff(), BigInt::from(36));
+    }
+}
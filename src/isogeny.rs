@@ -0,0 +1,352 @@
+use num_bigint::BigInt;
+
+use crate::{
+    elliptic_curve::{AffinePoint, EllipticCurve},
+    field_element::FieldElement,
+    finite_field::FiniteField,
+    polynomial::Polynomial,
+};
+
+// Isogenies with Vélu's formulas.
+//
+// An isogeny φ: E -> E' is a rational map that is also a group homomorphism.
+// Up to isomorphism it is determined by its kernel, a finite subgroup G of E,
+// and Vélu's formulas give both E' and φ from the points of G. Let S hold the
+// point of order 2 of G, if any, together with one point out of each pair
+// {Q, -Q} of the other non-zero points, and for Q = (x_Q, y_Q) in S let
+//
+//     g_Q = 3x_Q^2 + a,   v_Q = g_Q or 2g_Q when 2Q ≠ O,   u_Q = 4y_Q^2.
+//
+// With v = Σ v_Q and w = Σ (u_Q + x_Q v_Q), the codomain is
+//
+//     E': y^2 = x^3 + (a - 5v)x + (b - 7w)
+//
+// and φ(x, y) = (X(x), y X'(x)), where
+//
+//     X(x) = x + Σ (v_Q / (x - x_Q) + u_Q / (x - x_Q)^2).
+//
+// The degree of φ is the order ℓ of G, and pairings on both curves are
+// related by e'(φ(P), φ(Q)) = e(P, Q)^ℓ.
+
+type Poly<M> = Polynomial<FieldElement<M>>;
+
+fn constant<M: FiniteField>(n: i64) -> FieldElement<M> {
+    FieldElement::new(M::from_coefficients(&[BigInt::from(n)]))
+}
+
+fn derivative<M: FiniteField>(p: &Poly<M>) -> Poly<M> {
+    let coefficients: Vec<FieldElement<M>> = p
+        .coefficients()
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, c)| constant::<M>(i as i64) * c.clone())
+        .collect();
+    if coefficients.is_empty() {
+        Polynomial::new(vec![FieldElement::zero()])
+    } else {
+        Polynomial::new(coefficients)
+    }
+}
+
+/// A rational map (x, y) -> (x_num(x) / x_den(x), y * y_num(x) / y_den(x)),
+/// the form of the isogenies computed here and of the ones RFC 9380 gives
+/// for the simplified SWU map. The polynomials are listed lowest degree first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IsogenyMap<M: FiniteField> {
+    pub x_num: Vec<FieldElement<M>>,
+    pub x_den: Vec<FieldElement<M>>,
+    pub y_num: Vec<FieldElement<M>>,
+    pub y_den: Vec<FieldElement<M>>,
+}
+
+impl<M: FiniteField> IsogenyMap<M> {
+    /// Applies the map, sending the points where a denominator vanishes to
+    /// the point at infinity.
+    pub fn apply<E, F>(&self, p: &AffinePoint<E>) -> AffinePoint<F>
+    where
+        E: EllipticCurve<BaseField = M>,
+        F: EllipticCurve<BaseField = M>,
+    {
+        let evaluate = |coefficients: &[FieldElement<M>], x: &FieldElement<M>| {
+            coefficients
+                .iter()
+                .rev()
+                .fold(FieldElement::zero(), |acc, c| acc * x.clone() + c.clone())
+        };
+        match p {
+            AffinePoint::XY(x, y) => {
+                let x_den = evaluate(&self.x_den, x);
+                let y_den = evaluate(&self.y_den, x);
+                if x_den.is_zero() || y_den.is_zero() {
+                    return AffinePoint::Infinity;
+                }
+                AffinePoint::new_unchecked(
+                    evaluate(&self.x_num, x) / x_den,
+                    y.clone() * evaluate(&self.y_num, x) / y_den,
+                )
+            }
+            AffinePoint::Infinity => AffinePoint::Infinity,
+        }
+    }
+}
+
+/// A separable isogeny with a cyclic kernel, computed with Vélu's formulas.
+#[derive(Debug, Clone)]
+pub struct Isogeny<E: EllipticCurve> {
+    kernel: Vec<AffinePoint<E>>,
+    a: FieldElement<E::BaseField>,
+    b: FieldElement<E::BaseField>,
+    map: IsogenyMap<E::BaseField>,
+}
+
+impl<E: EllipticCurve> Isogeny<E> {
+    /// Computes the isogeny whose kernel is generated by the given point,
+    /// which must have a small order since the whole kernel is listed.
+    pub fn from_kernel(generator: &AffinePoint<E>) -> Self {
+        assert!(!generator.is_inf(), "the kernel must not be trivial");
+        let mut kernel = vec![AffinePoint::Infinity, generator.clone()];
+        loop {
+            let next = kernel.last().unwrap().clone() + generator.clone();
+            if next.is_inf() {
+                break;
+            }
+            kernel.push(next);
+        }
+
+        let (mut v, mut w) = (FieldElement::zero(), FieldElement::zero());
+        let mut terms = vec![];
+        for q in &kernel[1..=kernel.len() / 2] {
+            let (xq, yq) = q.xy().unwrap();
+            let g = constant(3) * xq.clone() * xq.clone() + E::a();
+            let (vq, uq) = if yq.is_zero() {
+                (g, FieldElement::zero())
+            } else {
+                (g.clone() + g, constant(4) * yq.clone() * yq)
+            };
+            v = v + vq.clone();
+            w = w + uq.clone() + xq.clone() * vq.clone();
+            terms.push((xq, vq, uq));
+        }
+
+        // X = N / D with D = h^2, h being the product of the x - x_Q
+        let one = Polynomial::new(vec![FieldElement::one()]);
+        let linear = |xq: &FieldElement<E::BaseField>| {
+            Polynomial::new(vec![-xq.clone(), FieldElement::one()])
+        };
+        let h = terms.iter().fold(one, |acc, (xq, _, _)| acc * linear(xq));
+        let d = h.clone() * h;
+        let mut n = linear(&FieldElement::zero()) * d.clone();
+        for (xq, vq, uq) in &terms {
+            let l = linear(xq);
+            let rest = d.clone() / (l.clone() * l.clone());
+            n = n + (l * vq.clone() + Polynomial::new(vec![uq.clone()])) * rest;
+        }
+
+        // Y = y X'(x) = y (N'D - ND') / D^2
+        let y_num = derivative(&n) * d.clone() - n.clone() * derivative(&d);
+        let map = IsogenyMap {
+            x_num: n.coefficients().to_vec(),
+            x_den: d.coefficients().to_vec(),
+            y_num: y_num.coefficients().to_vec(),
+            y_den: (d.clone() * d).coefficients().to_vec(),
+        };
+
+        Self {
+            kernel,
+            a: E::a() - constant(5) * v,
+            b: E::b() - constant(7) * w,
+            map,
+        }
+    }
+
+    /// The degree ℓ of the isogeny, the size of its kernel.
+    pub fn degree(&self) -> usize {
+        self.kernel.len()
+    }
+
+    /// The points of the kernel, starting with the point at infinity.
+    pub fn kernel(&self) -> &[AffinePoint<E>] {
+        &self.kernel
+    }
+
+    /// The coefficients (a', b') of the codomain y^2 = x^3 + a'x + b'.
+    pub fn codomain(&self) -> (FieldElement<E::BaseField>, FieldElement<E::BaseField>) {
+        (self.a.clone(), self.b.clone())
+    }
+
+    /// The isogeny as a rational map.
+    pub fn rational_map(&self) -> &IsogenyMap<E::BaseField> {
+        &self.map
+    }
+
+    /// Whether the curve F is the codomain of the isogeny.
+    pub fn is_codomain<F: EllipticCurve<BaseField = E::BaseField>>(&self) -> bool {
+        F::a() == self.a && F::b() == self.b
+    }
+
+    /// Evaluates the isogeny on a point, F being the codomain.
+    pub fn evaluate<F: EllipticCurve<BaseField = E::BaseField>>(
+        &self,
+        p: &AffinePoint<E>,
+    ) -> AffinePoint<F> {
+        assert!(
+            self.is_codomain::<F>(),
+            "the curve is not the codomain of the isogeny"
+        );
+        self.map.apply(p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{MoonMath, TinyJJ},
+        fields::{Fe13_4, Fe43_6, Ff13, Ff13_4, Ff43, Ff43_6},
+        pairing::Pairing,
+    };
+
+    use super::*;
+
+    /// y^2 = x^3 + x, the codomain of the 2-isogeny of TinyJJ with kernel (4, 0).
+    #[derive(Debug, Clone, PartialEq)]
+    struct TinyJJIso;
+
+    impl EllipticCurve for TinyJJIso {
+        type BaseField = Ff13_4;
+        type ScalarField = Ff13;
+
+        fn a() -> Fe13_4 {
+            Polynomial::from(vec![1]).into()
+        }
+
+        fn b() -> Fe13_4 {
+            Polynomial::from(vec![0]).into()
+        }
+
+        fn generator() -> AffinePoint<Self> {
+            AffinePoint::try_new(
+                Polynomial::from(vec![2]).into(),
+                Polynomial::from(vec![6]).into(),
+            )
+            .unwrap()
+        }
+    }
+
+    /// y^2 = x^3 + 10, the codomain of the 3-isogeny of MoonMath with kernel
+    /// generated by (0, 7).
+    #[derive(Debug, Clone, PartialEq)]
+    struct MoonMathIso;
+
+    impl EllipticCurve for MoonMathIso {
+        type BaseField = Ff43_6;
+        type ScalarField = Ff43;
+
+        fn a() -> Fe43_6 {
+            Polynomial::from(vec![0]).into()
+        }
+
+        fn b() -> Fe43_6 {
+            Polynomial::from(vec![10]).into()
+        }
+
+        fn generator() -> AffinePoint<Self> {
+            AffinePoint::try_new(
+                Polynomial::from(vec![1]).into(),
+                Polynomial::from(vec![21]).into(),
+            )
+            .unwrap()
+        }
+    }
+
+    fn tinyjj_isogeny() -> Isogeny<TinyJJ> {
+        let kernel = AffinePoint::try_new(
+            Polynomial::from(vec![4]).into(),
+            Polynomial::from(vec![0]).into(),
+        )
+        .unwrap();
+        Isogeny::from_kernel(&kernel)
+    }
+
+    fn moonmath_isogeny() -> Isogeny<MoonMath> {
+        let kernel = AffinePoint::try_new(
+            Polynomial::from(vec![0]).into(),
+            Polynomial::from(vec![7]).into(),
+        )
+        .unwrap();
+        Isogeny::from_kernel(&kernel)
+    }
+
+    #[test]
+    fn test_velu_codomain() {
+        let phi = tinyjj_isogeny();
+        assert_eq!(phi.degree(), 2);
+        assert!(phi.is_codomain::<TinyJJIso>());
+
+        let phi = moonmath_isogeny();
+        assert_eq!(phi.degree(), 3);
+        assert!(phi.is_codomain::<MoonMathIso>());
+        assert_eq!(phi.codomain(), (MoonMathIso::a(), MoonMathIso::b()));
+    }
+
+    #[test]
+    fn test_velu_evaluate() {
+        let phi = moonmath_isogeny();
+        for p in phi.kernel() {
+            assert!(phi.evaluate::<MoonMathIso>(p).is_inf());
+        }
+
+        // φ sends points to the codomain and is a homomorphism
+        let phi = tinyjj_isogeny();
+        let p = TinyJJ::generator();
+        let q = p.double() + p.clone();
+        let image = phi.evaluate::<TinyJJIso>(&p);
+        let (x, y) = image.xy().unwrap();
+        assert!(AffinePoint::<TinyJJIso>::is_on_curve(&x, &y));
+        assert_eq!(
+            phi.evaluate::<TinyJJIso>(&(p.clone() + q.clone())),
+            image + phi.evaluate::<TinyJJIso>(&q)
+        );
+        assert_eq!(
+            phi.evaluate::<TinyJJIso>(&-p.clone()),
+            -phi.evaluate::<TinyJJIso>(&p)
+        );
+    }
+
+    #[test]
+    fn test_velu_pairing() {
+        // e'(φP, φQ) = e(P, Q)^ℓ
+        let phi = tinyjj_isogeny();
+        let p = AffinePoint::<TinyJJ>::try_new_g1(
+            Polynomial::from(vec![8]).into(),
+            Polynomial::from(vec![8]).into(),
+        )
+        .unwrap();
+        let q = AffinePoint::<TinyJJ>::try_new_g2(
+            Polynomial::from(vec![7, 0, 4]).into(),
+            Polynomial::from(vec![0, 10, 0, 5]).into(),
+        )
+        .unwrap();
+        let (phi_p, phi_q) = (phi.evaluate::<TinyJJIso>(&p), phi.evaluate::<TinyJJIso>(&q));
+        assert_eq!(
+            TinyJJIso::tate_pairing(&phi_p, &phi_q),
+            TinyJJ::tate_pairing(&p, &q).pow_bigint(&BigInt::from(2))
+        );
+
+        let phi = moonmath_isogeny();
+        let p = AffinePoint::<MoonMath>::try_new_g1(
+            Polynomial::from(vec![27]).into(),
+            Polynomial::from(vec![34]).into(),
+        )
+        .unwrap();
+        let q = MoonMath::generator();
+        let (phi_p, phi_q) = (
+            phi.evaluate::<MoonMathIso>(&p),
+            phi.evaluate::<MoonMathIso>(&q),
+        );
+        assert_eq!(
+            MoonMathIso::tate_pairing(&phi_p, &phi_q),
+            MoonMath::tate_pairing(&p, &q).pow_bigint(&BigInt::from(3))
+        );
+    }
+}
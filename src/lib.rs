@@ -1,10 +1,31 @@
 #![allow(unused)]
 
+pub mod ate;
+pub mod constant_time;
 pub mod curves;
 pub mod elliptic_curve;
 pub mod field_element;
 pub mod fields;
 pub mod finite_field;
+pub mod glv;
+pub mod hash_to_curve;
+pub mod isogeny;
+pub mod matrix;
+pub mod models;
+pub mod msm;
+pub mod number_theory;
 pub mod pairing;
+pub mod point_counting;
 pub mod polynomial;
+pub mod projective;
+pub mod scalar_mul;
+pub mod schoof;
+pub mod serialization;
+pub mod sha256;
+pub mod subfield;
+pub mod subgroups;
+pub mod torsion;
+pub mod twist;
+pub mod validation;
+pub mod weierstrass;
 pub mod logger;
@@ -1,14 +1,24 @@
-use pairings_from_scratch::{curves::TinyJJ, elliptic_curve::AffinePoint, polynomial::Polynomial};
+use pairings_from_scratch::{
+    curves::{MoonMath, TinyJJ},
+    elliptic_curve::AffinePoint,
+    polynomial::Polynomial,
+    torsion::print_torsion_report,
+};
 
 fn main() {
-    let p = AffinePoint::<TinyJJ>::new_xy(
+    let p = AffinePoint::<TinyJJ>::try_new_g1(
         Polynomial::from(vec![8]).into(),
         Polynomial::from(vec![8]).into(),
-    );
-    let q = AffinePoint::<TinyJJ>::new_xy(
+    )
+    .unwrap();
+    let q = AffinePoint::<TinyJJ>::try_new_g2(
         Polynomial::from(vec![7, 0, 4]).into(),
         Polynomial::from(vec![0, 10, 0, 5]).into(),
-    );
+    )
+    .unwrap();
     let result = p.pairing(&q);
     println!("Output from pairing p and q: {}", result);
+
+    print_torsion_report::<TinyJJ>();
+    print_torsion_report::<MoonMath>();
 }
@@ -0,0 +1,491 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Add, Index, IndexMut, Mul, Neg, Sub},
+};
+
+use crate::{field_element::FieldElement, finite_field::FiniteField};
+
+/// A dense matrix stored in row-major order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix<F> {
+    rows: usize,
+    cols: usize,
+    data: Vec<F>,
+}
+
+impl<M: FiniteField> Matrix<FieldElement<M>> {
+    /// Creates a `rows x cols` matrix from its entries given in row-major order.
+    pub fn new(rows: usize, cols: usize, data: Vec<FieldElement<M>>) -> Self {
+        assert_eq!(data.len(), rows * cols, "wrong number of entries");
+        Self { rows, cols, data }
+    }
+
+    /// Creates a matrix from a list of rows of equal length.
+    pub fn from_rows(rows: Vec<Vec<FieldElement<M>>>) -> Self {
+        let cols = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "rows differ in length"
+        );
+        let n = rows.len();
+        Self::new(n, cols, rows.into_iter().flatten().collect())
+    }
+
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        Self::new(rows, cols, vec![FieldElement::zero(); rows * cols])
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut result = Self::zero(n, n);
+        for i in 0..n {
+            result[(i, i)] = FieldElement::one();
+        }
+        result
+    }
+
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[inline]
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    /// Returns a slice of the entries in row `i`.
+    pub fn row(&self, i: usize) -> &[FieldElement<M>] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// Returns a copy of the entries in column `j`.
+    pub fn col(&self, j: usize) -> Vec<FieldElement<M>> {
+        (0..self.rows).map(|i| self[(i, j)].clone()).collect()
+    }
+
+    pub fn transpose(&self) -> Self {
+        let data = (0..self.cols).flat_map(|j| self.col(j)).collect();
+        Self::new(self.cols, self.rows, data)
+    }
+
+    /// Multiplies the matrix by a column vector.
+    pub fn mul_vec(&self, v: &[FieldElement<M>]) -> Vec<FieldElement<M>> {
+        assert_eq!(v.len(), self.cols, "dimension mismatch");
+        (0..self.rows).map(|i| dot(self.row(i), v)).collect()
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        if a != b {
+            for j in 0..self.cols {
+                self.data.swap(a * self.cols + j, b * self.cols + j);
+            }
+        }
+    }
+
+    /// Brings the matrix into reduced row echelon form using Gauss-Jordan elimination.
+    /// Returns the reduced matrix, the indices of the pivot columns and
+    /// the number of row swaps performed along the way.
+    fn reduce(&self) -> (Self, Vec<usize>, usize) {
+        let mut m = self.clone();
+        let mut pivots = vec![];
+        let mut swaps = 0;
+
+        for j in 0..m.cols {
+            let row = pivots.len();
+            if row == m.rows {
+                break;
+            }
+
+            // find a row with a non-zero entry in column j
+            let Some(p) = (row..m.rows).find(|&i| !m[(i, j)].is_zero()) else {
+                continue;
+            };
+            if p != row {
+                m.swap_rows(p, row);
+                swaps += 1;
+            }
+
+            // scale the pivot row so that the pivot becomes 1
+            let inv = m[(row, j)].inverse();
+            for c in j..m.cols {
+                m[(row, c)] = m[(row, c)].clone() * inv.clone();
+            }
+
+            // eliminate column j from every other row
+            for i in 0..m.rows {
+                if i == row || m[(i, j)].is_zero() {
+                    continue;
+                }
+                let factor = m[(i, j)].clone();
+                for c in j..m.cols {
+                    m[(i, c)] = m[(i, c)].clone() - factor.clone() * m[(row, c)].clone();
+                }
+            }
+
+            pivots.push(j);
+        }
+
+        (m, pivots, swaps)
+    }
+
+    /// Returns the reduced row echelon form of the matrix.
+    pub fn rref(&self) -> Self {
+        self.reduce().0
+    }
+
+    /// The rank is the number of pivots in the row echelon form.
+    pub fn rank(&self) -> usize {
+        self.reduce().1.len()
+    }
+
+    /// Computes the determinant by Gaussian elimination: the determinant is
+    /// the product of the pivots, with the sign flipped for every row swap.
+    pub fn determinant(&self) -> FieldElement<M> {
+        assert!(self.is_square(), "determinant of a non-square matrix");
+        let mut m = self.clone();
+        let mut det = FieldElement::one();
+
+        for j in 0..m.cols {
+            let Some(p) = (j..m.rows).find(|&i| !m[(i, j)].is_zero()) else {
+                return FieldElement::zero();
+            };
+            if p != j {
+                m.swap_rows(p, j);
+                det = -det;
+            }
+
+            let pivot = m[(j, j)].clone();
+            det = det * pivot.clone();
+
+            let inv = pivot.inverse();
+            for i in j + 1..m.rows {
+                if m[(i, j)].is_zero() {
+                    continue;
+                }
+                let factor = m[(i, j)].clone() * inv.clone();
+                for c in j..m.cols {
+                    m[(i, c)] = m[(i, c)].clone() - factor.clone() * m[(j, c)].clone();
+                }
+            }
+        }
+
+        det
+    }
+
+    /// Computes the inverse by reducing the augmented matrix [A | I] to [I | A^-1].
+    /// Returns `None` if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        assert!(self.is_square(), "inverse of a non-square matrix");
+        let n = self.rows;
+        let augmented = self.augment(&Self::identity(n));
+        let (reduced, pivots, _) = augmented.reduce();
+
+        // A is invertible exactly when its own columns hold all the pivots
+        if pivots.iter().copied().ne(0..n) {
+            return None;
+        }
+
+        let data = (0..n).flat_map(|i| reduced.row(i)[n..].to_vec()).collect();
+        Some(Self::new(n, n, data))
+    }
+
+    /// Returns a basis of the kernel (nullspace), i.e. of all vectors `v` with `A * v = 0`.
+    pub fn kernel(&self) -> Vec<Vec<FieldElement<M>>> {
+        let (reduced, pivots, _) = self.reduce();
+        let free = (0..self.cols).filter(|j| !pivots.contains(j));
+
+        // every free variable gives one basis vector: set it to 1,
+        // the other free variables to 0 and solve for the pivot variables
+        free.map(|f| {
+            let mut v = vec![FieldElement::zero(); self.cols];
+            v[f] = FieldElement::one();
+            for (row, &p) in pivots.iter().enumerate() {
+                v[p] = -reduced[(row, f)].clone();
+            }
+            v
+        })
+        .collect()
+    }
+
+    /// Solves the linear system `A * x = b`, returning one solution if the
+    /// system is consistent. All other solutions differ from it by an element
+    /// of the kernel.
+    pub fn solve(&self, b: &[FieldElement<M>]) -> Option<Vec<FieldElement<M>>> {
+        assert_eq!(b.len(), self.rows, "dimension mismatch");
+        let column = Self::new(self.rows, 1, b.to_vec());
+        let (reduced, pivots, _) = self.augment(&column).reduce();
+
+        // a pivot in the last column means the row 0 = 1 appeared
+        if pivots.last() == Some(&self.cols) {
+            return None;
+        }
+
+        let mut x = vec![FieldElement::zero(); self.cols];
+        for (row, &p) in pivots.iter().enumerate() {
+            x[p] = reduced[(row, self.cols)].clone();
+        }
+        Some(x)
+    }
+
+    /// Places `other` to the right of `self`.
+    fn augment(&self, other: &Self) -> Self {
+        assert_eq!(self.rows, other.rows, "dimension mismatch");
+        let data = (0..self.rows)
+            .flat_map(|i| [self.row(i), other.row(i)].concat())
+            .collect();
+        Self::new(self.rows, self.cols + other.cols, data)
+    }
+}
+
+fn dot<M: FiniteField>(a: &[FieldElement<M>], b: &[FieldElement<M>]) -> FieldElement<M> {
+    a.iter().zip(b).fold(FieldElement::zero(), |acc, (x, y)| {
+        acc + x.clone() * y.clone()
+    })
+}
+
+impl<F> Index<(usize, usize)> for Matrix<F> {
+    type Output = F;
+
+    fn index(&self, (i, j): (usize, usize)) -> &F {
+        assert!(i < self.rows && j < self.cols, "index out of bounds");
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<F> IndexMut<(usize, usize)> for Matrix<F> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut F {
+        assert!(i < self.rows && j < self.cols, "index out of bounds");
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl<M: FiniteField> Display for Matrix<FieldElement<M>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in 0..self.rows {
+            let row: Vec<String> = self.row(i).iter().map(|c| c.to_string()).collect();
+            writeln!(f, "[{}]", row.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+// Implement operator overloading
+
+/// Entry-wise addition of two matrices of the same dimensions.
+impl<M: FiniteField> Add for Matrix<FieldElement<M>> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        assert!(
+            self.rows == rhs.rows && self.cols == rhs.cols,
+            "dimension mismatch"
+        );
+        let data = self
+            .data
+            .into_iter()
+            .zip(rhs.data)
+            .map(|(a, b)| a + b)
+            .collect();
+        Self::new(self.rows, self.cols, data)
+    }
+}
+
+impl<M: FiniteField> Neg for Matrix<FieldElement<M>> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        let data = self.data.into_iter().map(|a| -a).collect();
+        Self::new(self.rows, self.cols, data)
+    }
+}
+
+impl<M: FiniteField> Sub for Matrix<FieldElement<M>> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+/// Row-by-column matrix multiplication.
+impl<M: FiniteField> Mul for Matrix<FieldElement<M>> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        assert_eq!(self.cols, rhs.rows, "dimension mismatch");
+        let columns: Vec<_> = (0..rhs.cols).map(|j| rhs.col(j)).collect();
+        let data = (0..self.rows)
+            .flat_map(|i| columns.iter().map(move |c| (i, c)))
+            .map(|(i, c)| dot(self.row(i), c))
+            .collect();
+        Self::new(self.rows, rhs.cols, data)
+    }
+}
+
+/// Multiplication of every entry by a scalar.
+impl<M: FiniteField> Mul<FieldElement<M>> for Matrix<FieldElement<M>> {
+    type Output = Self;
+
+    fn mul(self, scalar: FieldElement<M>) -> Self {
+        let data = self.data.into_iter().map(|a| a * scalar.clone()).collect();
+        Self::new(self.rows, self.cols, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fields::{Fe13, Fe13_2},
+        polynomial::Polynomial,
+    };
+
+    use super::*;
+
+    fn matrix(rows: Vec<Vec<i16>>) -> Matrix<Fe13> {
+        Matrix::from_rows(
+            rows.into_iter()
+                .map(|row| row.into_iter().map(Fe13::new).collect())
+                .collect(),
+        )
+    }
+
+    fn vector(values: Vec<i16>) -> Vec<Fe13> {
+        values.into_iter().map(Fe13::new).collect()
+    }
+
+    #[test]
+    fn test_matrix_mul() {
+        let a = matrix(vec![vec![1, 2], vec![3, 4]]);
+        let b = matrix(vec![vec![5, 6], vec![7, 8]]);
+        // [[19, 22], [43, 50]] mod 13
+        assert_eq!(a * b, matrix(vec![vec![6, 9], vec![4, 11]]));
+    }
+
+    #[test]
+    fn test_matrix_mul_identity() {
+        let a = matrix(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(a.clone() * Matrix::identity(3), a.clone());
+        assert_eq!(Matrix::identity(2) * a.clone(), a);
+    }
+
+    #[test]
+    fn test_matrix_transpose() {
+        let a = matrix(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(
+            a.transpose(),
+            matrix(vec![vec![1, 4], vec![2, 5], vec![3, 6]])
+        );
+    }
+
+    #[test]
+    fn test_matrix_rref() {
+        let a = matrix(vec![vec![1, 2, 3], vec![2, 4, 6], vec![1, 0, 1]]);
+        let expected = matrix(vec![vec![1, 0, 1], vec![0, 1, 1], vec![0, 0, 0]]);
+        assert_eq!(a.rref(), expected);
+    }
+
+    #[test]
+    fn test_matrix_rank() {
+        assert_eq!(Matrix::<Fe13>::identity(4).rank(), 4);
+        assert_eq!(Matrix::<Fe13>::zero(3, 2).rank(), 0);
+        let a = matrix(vec![vec![1, 2, 3], vec![2, 4, 6], vec![1, 0, 1]]);
+        assert_eq!(a.rank(), 2);
+        // rows are linearly dependent only modulo 13: (1, 5) * 3 = (3, 15) = (3, 2)
+        let b = matrix(vec![vec![1, 5], vec![3, 2]]);
+        assert_eq!(b.rank(), 1);
+    }
+
+    #[test]
+    fn test_matrix_determinant() {
+        let a = matrix(vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(a.determinant(), Fe13::new(-2));
+
+        let b = matrix(vec![vec![0, 1, 2], vec![1, 0, 3], vec![4, -3, 8]]);
+        // -1 * (8 - 12) + 2 * (-3) = -2
+        assert_eq!(b.determinant(), Fe13::new(-2));
+
+        let singular = matrix(vec![vec![1, 2, 3], vec![2, 4, 6], vec![1, 0, 1]]);
+        assert_eq!(singular.determinant(), Fe13::zero());
+    }
+
+    #[test]
+    fn test_matrix_determinant_multiplicative() {
+        let a = matrix(vec![vec![2, 7, 1], vec![0, 3, 5], vec![9, 4, 4]]);
+        let b = matrix(vec![vec![1, 1, 0], vec![6, 2, 11], vec![3, 0, 7]]);
+        assert_eq!(
+            (a.clone() * b.clone()).determinant(),
+            a.determinant() * b.determinant()
+        );
+    }
+
+    #[test]
+    fn test_matrix_inverse() {
+        let a = matrix(vec![vec![2, 7, 1], vec![0, 3, 5], vec![9, 4, 4]]);
+        let inv = a.inverse().unwrap();
+        assert_eq!(a.clone() * inv.clone(), Matrix::identity(3));
+        assert_eq!(inv * a, Matrix::identity(3));
+
+        let singular = matrix(vec![vec![1, 5], vec![3, 2]]);
+        assert!(singular.inverse().is_none());
+    }
+
+    #[test]
+    fn test_matrix_inverse_ext_field() {
+        let x: Fe13_2 = Polynomial::from(vec![3, 5]).into();
+        let y: Fe13_2 = Polynomial::from(vec![0, 1]).into();
+        let a = Matrix::from_rows(vec![
+            vec![x.clone(), y.clone()],
+            vec![y.clone(), Fe13_2::one()],
+        ]);
+        assert_eq!(a.determinant(), x - y.clone() * y);
+        let inv = a.inverse().unwrap();
+        assert_eq!(a * inv, Matrix::identity(2));
+    }
+
+    #[test]
+    fn test_matrix_kernel() {
+        let a = matrix(vec![vec![1, 2, 3], vec![2, 4, 6], vec![1, 0, 1]]);
+        let kernel = a.kernel();
+        assert_eq!(kernel.len(), 1);
+        assert_eq!(kernel[0], vector(vec![-1, -1, 1]));
+        assert_eq!(a.mul_vec(&kernel[0]), vector(vec![0, 0, 0]));
+
+        let b = matrix(vec![vec![1, 2, 0, 4], vec![0, 0, 1, 3]]);
+        let kernel = b.kernel();
+        assert_eq!(kernel.len(), 2);
+        for v in kernel.iter() {
+            assert_eq!(b.mul_vec(v), vector(vec![0, 0]));
+        }
+
+        assert!(Matrix::<Fe13>::identity(3).kernel().is_empty());
+    }
+
+    #[test]
+    fn test_matrix_solve() {
+        let a = matrix(vec![vec![2, 7, 1], vec![0, 3, 5], vec![9, 4, 4]]);
+        let b = vector(vec![1, 2, 3]);
+        let x = a.solve(&b).unwrap();
+        assert_eq!(a.mul_vec(&x), b);
+        assert_eq!(x, a.inverse().unwrap().mul_vec(&b));
+    }
+
+    #[test]
+    fn test_matrix_solve_underdetermined() {
+        let a = matrix(vec![vec![1, 2, 3], vec![2, 4, 6], vec![1, 0, 1]]);
+        let b = vector(vec![1, 2, 5]);
+        let x = a.solve(&b).unwrap();
+        assert_eq!(a.mul_vec(&x), b);
+    }
+
+    #[test]
+    fn test_matrix_solve_inconsistent() {
+        let a = matrix(vec![vec![1, 2, 3], vec![2, 4, 6], vec![1, 0, 1]]);
+        let b = vector(vec![1, 1, 1]);
+        assert!(a.solve(&b).is_none());
+    }
+}
@@ -0,0 +1,464 @@
+use std::ops::{Add, Neg};
+
+use num_bigint::BigInt;
+use num_traits::{Signed, Zero};
+
+use crate::{
+    elliptic_curve::{AffinePoint, CurveError, EllipticCurve},
+    field_element::FieldElement,
+    finite_field::FiniteField,
+    pairing::Pairing,
+};
+
+// Other curve models.
+//
+// A twisted Edwards curve a*x^2 + y^2 = 1 + d*x^2*y^2 has a single addition
+// formula that also covers doubling and the neutral element (0, 1). It is
+// birationally equivalent to the Montgomery curve B*v^2 = u^3 + A*u^2 + u with
+//
+//     A = 2(a + d) / (a - d),  B = 4 / (a - d),  u = (1 + y) / (1 - y),  v = u / x
+//
+// and the Montgomery curve is isomorphic to the short Weierstrass curve
+//
+//     y^2 = x^3 + (3 - A^2) / (3B^2) * x + (2A^3 - 9A) / (27B^3)
+//
+// through x = u/B + A/(3B), y = v/B. Pairings of points in these models are
+// computed on their short Weierstrass form.
+
+/// The short Weierstrass form of a twisted Edwards curve.
+pub type EdwardsWeierstrass<C> =
+    <<C as TwistedEdwardsCurve>::Montgomery as MontgomeryCurve>::Weierstrass;
+
+fn small<M: FiniteField>(n: usize) -> FieldElement<M> {
+    (0..n).fold(FieldElement::zero(), |acc, _| acc + FieldElement::one())
+}
+
+pub trait MontgomeryCurve: Clone + PartialEq {
+    type BaseField: FiniteField;
+    /// The isomorphic short Weierstrass curve.
+    type Weierstrass: EllipticCurve<BaseField = Self::BaseField>;
+
+    fn a() -> FieldElement<Self::BaseField>;
+    fn b() -> FieldElement<Self::BaseField>;
+
+    /// Checks that `Weierstrass` has the coefficients derived from A and B.
+    fn is_valid_model() -> bool {
+        let (a, b) = (Self::a(), Self::b());
+        let a2 = a.clone() * a.clone();
+        let b2 = b.clone() * b.clone();
+
+        let w_a = (small(3) - a2.clone()) / (small(3) * b2.clone());
+        let w_b = (small(2) * a2 * a.clone() - small(9) * a) / (small(27) * b2 * b);
+        w_a == Self::Weierstrass::a() && w_b == Self::Weierstrass::b()
+    }
+}
+
+pub trait TwistedEdwardsCurve: Clone + PartialEq {
+    type BaseField: FiniteField;
+    /// The birationally equivalent Montgomery curve.
+    type Montgomery: MontgomeryCurve<BaseField = Self::BaseField>;
+
+    fn a() -> FieldElement<Self::BaseField>;
+    fn d() -> FieldElement<Self::BaseField>;
+
+    /// Checks that `Montgomery` has the coefficients derived from a and d,
+    /// and that its own short Weierstrass form is consistent.
+    fn is_valid_model() -> bool {
+        let (a, d) = (Self::a(), Self::d());
+        let diff = a.clone() - d.clone();
+        Self::Montgomery::a() == small(2) * (a + d) / diff.clone()
+            && Self::Montgomery::b() == small(4) / diff
+            && Self::Montgomery::is_valid_model()
+    }
+
+    /// The addition law has no exceptions when a is a square and d is not.
+    fn is_complete() -> bool {
+        Self::a().is_square() && !Self::d().is_square()
+    }
+
+    /// The Tate pairing of two points, computed on the short Weierstrass form.
+    fn tate_pairing(
+        p: &EdwardsPoint<Self>,
+        q: &EdwardsPoint<Self>,
+    ) -> FieldElement<Self::BaseField> {
+        EdwardsWeierstrass::<Self>::tate_pairing(&p.to_weierstrass(), &q.to_weierstrass())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum MontgomeryPoint<C: MontgomeryCurve> {
+    Infinity,
+    UV(FieldElement<C::BaseField>, FieldElement<C::BaseField>),
+}
+
+impl<C: MontgomeryCurve> MontgomeryPoint<C> {
+    pub fn try_new(
+        u: FieldElement<C::BaseField>,
+        v: FieldElement<C::BaseField>,
+    ) -> Result<Self, CurveError> {
+        if Self::is_on_curve(&u, &v) {
+            Ok(MontgomeryPoint::UV(u, v))
+        } else {
+            Err(CurveError::NotOnCurve)
+        }
+    }
+
+    pub fn is_on_curve(u: &FieldElement<C::BaseField>, v: &FieldElement<C::BaseField>) -> bool {
+        let u2 = u.clone() * u.clone();
+        C::b() * v.clone() * v.clone() == u2.clone() * u.clone() + C::a() * u2 + u.clone()
+    }
+
+    pub fn is_inf(&self) -> bool {
+        matches!(self, MontgomeryPoint::Infinity)
+    }
+
+    pub fn double(&self) -> Self {
+        match self {
+            MontgomeryPoint::UV(u, v) if !v.is_zero() => {
+                // (3u^2 + 2Au + 1) / 2Bv
+                let m = (small(3) * u.clone() * u.clone()
+                    + small(2) * C::a() * u.clone()
+                    + FieldElement::one())
+                    / (small(2) * C::b() * v.clone());
+                let u3 = C::b() * m.clone() * m.clone() - C::a() - u.clone() - u.clone();
+                let v3 = m * (u.clone() - u3.clone()) - v.clone();
+                MontgomeryPoint::UV(u3, v3)
+            }
+            _ => MontgomeryPoint::Infinity,
+        }
+    }
+
+    pub fn to_weierstrass(&self) -> AffinePoint<C::Weierstrass> {
+        match self {
+            MontgomeryPoint::UV(u, v) => {
+                let x = (u.clone() + C::a() / small(3)) / C::b();
+                AffinePoint::try_new(x, v.clone() / C::b()).expect("invalid Weierstrass model")
+            }
+            MontgomeryPoint::Infinity => AffinePoint::Infinity,
+        }
+    }
+
+    pub fn from_weierstrass(p: &AffinePoint<C::Weierstrass>) -> Self {
+        match p {
+            AffinePoint::XY(x, y) => {
+                let u = C::b() * x.clone() - C::a() / small(3);
+                MontgomeryPoint::UV(u, C::b() * y.clone())
+            }
+            AffinePoint::Infinity => MontgomeryPoint::Infinity,
+        }
+    }
+}
+
+impl<C: MontgomeryCurve> Add for MontgomeryPoint<C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        if self == other {
+            return self.double();
+        }
+
+        if self == -other.clone() {
+            return MontgomeryPoint::Infinity;
+        }
+
+        match (self, other) {
+            (MontgomeryPoint::Infinity, p) | (p, MontgomeryPoint::Infinity) => p,
+            (MontgomeryPoint::UV(u1, v1), MontgomeryPoint::UV(u2, v2)) => {
+                let m = (v2 - v1.clone()) / (u2.clone() - u1.clone());
+                let u3 = C::b() * m.clone() * m.clone() - C::a() - u1.clone() - u2;
+                let v3 = m * (u1 - u3.clone()) - v1;
+                MontgomeryPoint::UV(u3, v3)
+            }
+        }
+    }
+}
+
+impl<C: MontgomeryCurve> Neg for MontgomeryPoint<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self {
+            MontgomeryPoint::UV(u, v) => MontgomeryPoint::UV(u, -v),
+            MontgomeryPoint::Infinity => MontgomeryPoint::Infinity,
+        }
+    }
+}
+
+/// A point of a twisted Edwards curve. The neutral element is (0, 1),
+/// so no point at infinity is needed.
+#[derive(Debug, PartialEq, Clone)]
+pub struct EdwardsPoint<C: TwistedEdwardsCurve> {
+    pub x: FieldElement<C::BaseField>,
+    pub y: FieldElement<C::BaseField>,
+}
+
+impl<C: TwistedEdwardsCurve> EdwardsPoint<C> {
+    pub fn try_new(
+        x: FieldElement<C::BaseField>,
+        y: FieldElement<C::BaseField>,
+    ) -> Result<Self, CurveError> {
+        if Self::is_on_curve(&x, &y) {
+            Ok(EdwardsPoint { x, y })
+        } else {
+            Err(CurveError::NotOnCurve)
+        }
+    }
+
+    pub fn is_on_curve(x: &FieldElement<C::BaseField>, y: &FieldElement<C::BaseField>) -> bool {
+        let x2 = x.clone() * x.clone();
+        let y2 = y.clone() * y.clone();
+        C::a() * x2.clone() + y2.clone() == FieldElement::one() + C::d() * x2 * y2
+    }
+
+    pub fn identity() -> Self {
+        EdwardsPoint {
+            x: FieldElement::zero(),
+            y: FieldElement::one(),
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::identity()
+    }
+
+    pub fn double(&self) -> Self {
+        self.clone() + self.clone()
+    }
+
+    /// Double-and-add over the bits of |k|; a negative k multiplies the negated point.
+    pub fn mul_double_and_add(&self, k: &BigInt) -> Self {
+        let magnitude = k.magnitude();
+        let mut point = Self::identity();
+        for i in (0..magnitude.bits()).rev() {
+            point = point.double();
+            if magnitude.bit(i) {
+                point = point + self.clone();
+            }
+        }
+
+        if k.is_negative() {
+            -point
+        } else {
+            point
+        }
+    }
+
+    /// Maps the point to the Montgomery form. The identity goes to the point
+    /// at infinity and (0, -1) to the point (0, 0) of order two.
+    pub fn to_montgomery(&self) -> MontgomeryPoint<C::Montgomery> {
+        if self.x.is_zero() {
+            return if self.is_identity() {
+                MontgomeryPoint::Infinity
+            } else {
+                MontgomeryPoint::UV(FieldElement::zero(), FieldElement::zero())
+            };
+        }
+
+        let one = FieldElement::<C::BaseField>::one();
+        let u = (one.clone() + self.y.clone()) / (one - self.y.clone());
+        let v = u.clone() / self.x.clone();
+        MontgomeryPoint::UV(u, v)
+    }
+
+    /// The inverse of `to_montgomery`. Returns None for the points with
+    /// v = 0 or u = -1, which go to points at infinity of the Edwards curve.
+    /// They only exist when the addition law is incomplete.
+    pub fn from_montgomery(p: &MontgomeryPoint<C::Montgomery>) -> Option<Self> {
+        let one = FieldElement::<C::BaseField>::one();
+        match p {
+            MontgomeryPoint::Infinity => Some(Self::identity()),
+            MontgomeryPoint::UV(u, v) if u.is_zero() && v.is_zero() => Some(EdwardsPoint {
+                x: FieldElement::zero(),
+                y: -one,
+            }),
+            MontgomeryPoint::UV(u, v) if v.is_zero() || (u.clone() + one.clone()).is_zero() => None,
+            MontgomeryPoint::UV(u, v) => Some(EdwardsPoint {
+                x: u.clone() / v.clone(),
+                y: (u.clone() - one.clone()) / (u.clone() + one),
+            }),
+        }
+    }
+
+    pub fn to_weierstrass(&self) -> AffinePoint<EdwardsWeierstrass<C>> {
+        self.to_montgomery().to_weierstrass()
+    }
+
+    pub fn from_weierstrass(p: &AffinePoint<EdwardsWeierstrass<C>>) -> Option<Self> {
+        Self::from_montgomery(&MontgomeryPoint::from_weierstrass(p))
+    }
+
+    /// The unified addition law
+    ///   x3 = (x1*y2 + y1*x2) / (1 + d*x1*x2*y1*y2)
+    ///   y3 = (y1*y2 - a*x1*x2) / (1 - d*x1*x2*y1*y2)
+    /// which also doubles. On the exceptional pairs of incomplete curves, where
+    /// a denominator vanishes, the points are added in Weierstrass form, and
+    /// None is returned when the sum is a point at infinity of the Edwards model.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let one = FieldElement::<C::BaseField>::one();
+        let xx = self.x.clone() * other.x.clone();
+        let yy = self.y.clone() * other.y.clone();
+        let dxxyy = C::d() * xx.clone() * yy.clone();
+
+        let x_den = one.clone() + dxxyy.clone();
+        let y_den = one - dxxyy;
+        if x_den.is_zero() || y_den.is_zero() {
+            return Self::from_weierstrass(&(self.to_weierstrass() + other.to_weierstrass()));
+        }
+
+        let x3 = (self.x.clone() * other.y.clone() + self.y.clone() * other.x.clone()) / x_den;
+        let y3 = (yy - C::a() * xx) / y_den;
+        Some(EdwardsPoint { x: x3, y: y3 })
+    }
+}
+
+/// Addition through `checked_add`. Panics when the sum is a point at
+/// infinity of the Edwards model, which incomplete curves have.
+impl<C: TwistedEdwardsCurve> Add for EdwardsPoint<C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(&other)
+            .expect("the sum is a point at infinity of the Edwards model")
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Neg for EdwardsPoint<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        EdwardsPoint {
+            x: -self.x,
+            y: self.y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{TinyJJ, TinyJJEdwards, TinyJJMontgomery},
+        fields::Fe13_4,
+        polynomial::Polynomial,
+    };
+
+    use super::*;
+
+    fn fe(c: Vec<i16>) -> Fe13_4 {
+        Polynomial::from(c).into()
+    }
+
+    #[test]
+    fn test_models_valid() {
+        assert!(TinyJJMontgomery::is_valid_model());
+        assert!(TinyJJEdwards::is_valid_model());
+        // every element of F_13 is a square in F_13^4
+        assert!(!TinyJJEdwards::is_complete());
+    }
+
+    #[test]
+    fn test_models_edwards_point() {
+        let p = EdwardsPoint::<TinyJJEdwards>::try_new(fe(vec![1]), fe(vec![2]));
+        assert!(p.is_ok());
+        let p = EdwardsPoint::<TinyJJEdwards>::try_new(fe(vec![1]), fe(vec![3]));
+        assert_eq!(p, Err(CurveError::NotOnCurve));
+
+        let m = MontgomeryPoint::<TinyJJMontgomery>::try_new(fe(vec![0]), fe(vec![0]));
+        assert!(m.is_ok());
+        let m = MontgomeryPoint::<TinyJJMontgomery>::try_new(fe(vec![0]), fe(vec![1]));
+        assert_eq!(m, Err(CurveError::NotOnCurve));
+    }
+
+    #[test]
+    fn test_models_maps() {
+        let identity = EdwardsPoint::<TinyJJEdwards>::identity();
+        assert_eq!(identity.to_weierstrass(), AffinePoint::Infinity);
+        assert_eq!(
+            EdwardsPoint::from_weierstrass(&AffinePoint::Infinity),
+            Some(identity)
+        );
+
+        // (0, -1) has order two, like (4, 0)
+        let p = EdwardsPoint::<TinyJJEdwards>::try_new(fe(vec![0]), fe(vec![12])).unwrap();
+        let w = AffinePoint::<TinyJJ>::try_new(fe(vec![4]), fe(vec![0])).unwrap();
+        assert_eq!(p.to_weierstrass(), w);
+        assert_eq!(EdwardsPoint::from_weierstrass(&w), Some(p.clone()));
+        assert!(p.double().is_identity());
+
+        let p = EdwardsPoint::<TinyJJEdwards>::try_new(fe(vec![1]), fe(vec![2])).unwrap();
+        let w = p.to_weierstrass();
+        assert_eq!(EdwardsPoint::from_weierstrass(&w), Some(p.clone()));
+        assert_eq!(MontgomeryPoint::from_weierstrass(&w), p.to_montgomery());
+
+        let g = TinyJJ::generator();
+        let e = EdwardsPoint::<TinyJJEdwards>::from_weierstrass(&g).unwrap();
+        assert!(EdwardsPoint::<TinyJJEdwards>::is_on_curve(&e.x, &e.y));
+        assert_eq!(e.to_weierstrass(), g);
+    }
+
+    #[test]
+    fn test_models_addition() {
+        let p = EdwardsPoint::<TinyJJEdwards>::try_new(fe(vec![1]), fe(vec![2])).unwrap();
+        let w = p.to_weierstrass();
+
+        assert_eq!(p.double().to_weierstrass(), w.double());
+        assert_eq!(
+            (p.clone() + p.double()).to_weierstrass(),
+            w.clone() + w.double()
+        );
+        assert_eq!((-p.clone()).to_weierstrass(), -w.clone());
+        assert!((p.clone() + -p.clone()).is_identity());
+        assert_eq!(
+            p.mul_double_and_add(&BigInt::from(7)).to_weierstrass(),
+            w.mul_double_and_add(&BigInt::from(7))
+        );
+        assert_eq!(
+            p.mul_double_and_add(&BigInt::from(-3)).to_weierstrass(),
+            w.mul_double_and_add(&BigInt::from(-3))
+        );
+
+        let m = p.to_montgomery();
+        assert_eq!(m.double().to_weierstrass(), w.double());
+        assert_eq!(
+            (m.clone() + m.double()).to_weierstrass(),
+            w.clone() + w.double()
+        );
+        assert!((m.clone() + -m).is_inf());
+    }
+
+    #[test]
+    fn test_models_exceptional_addition() {
+        let g = TinyJJ::generator();
+        let edwards =
+            |k: i16| EdwardsPoint::<TinyJJEdwards>::from_weierstrass(&(g.clone() * k)).unwrap();
+        let exceptional = |p: &EdwardsPoint<TinyJJEdwards>, q: &EdwardsPoint<TinyJJEdwards>| {
+            let dxxyy = TinyJJEdwards::d() * p.x.clone() * q.x.clone() * p.y.clone() * q.y.clone();
+            dxxyy == Fe13_4::one() || dxxyy == -Fe13_4::one()
+        };
+
+        // a denominator of the addition law vanishes, but the sum is affine
+        let (p, q) = (edwards(1), edwards(121));
+        assert!(exceptional(&p, &q));
+        assert_eq!(p.checked_add(&q), Some(edwards(122)));
+        assert_eq!(p + q, edwards(122));
+
+        // here the sum is a point at infinity of the Edwards model
+        let (p, q) = (edwards(1), edwards(119));
+        assert!(exceptional(&p, &q));
+        assert_eq!(p.checked_add(&q), None);
+        assert!(EdwardsPoint::<TinyJJEdwards>::from_weierstrass(&(g * 120)).is_none());
+    }
+
+    #[test]
+    fn test_models_pairing() {
+        let p = AffinePoint::<TinyJJ>::try_new_g1(fe(vec![8]), fe(vec![8])).unwrap();
+        let q =
+            AffinePoint::<TinyJJ>::try_new_g2(fe(vec![7, 0, 4]), fe(vec![0, 10, 0, 5])).unwrap();
+
+        let p_ed = EdwardsPoint::<TinyJJEdwards>::from_weierstrass(&p).unwrap();
+        let q_ed = EdwardsPoint::<TinyJJEdwards>::from_weierstrass(&q).unwrap();
+        assert_eq!(
+            TinyJJEdwards::tate_pairing(&p_ed, &q_ed),
+            TinyJJ::tate_pairing(&p, &q)
+        );
+    }
+}
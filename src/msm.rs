@@ -0,0 +1,164 @@
+use num_bigint::BigInt;
+use num_traits::{Signed, Zero};
+
+use crate::{
+    elliptic_curve::{AffinePoint, EllipticCurve},
+    finite_field::{FiniteField, NonExtendedField},
+    projective::JacobianPoint,
+};
+
+// Multi-scalar multiplication: sum of s_i * P_i over many points.
+//
+// Pippenger's bucket method splits every scalar into c-bit windows. For each
+// window, every point is added once into the bucket matching its digit, and
+// the buckets are combined with a running sum, so that bucket j ends up being
+// counted j times. The windows are then combined with c doublings each, which
+// are shared by all points instead of being repeated for every one of them.
+
+/// Chooses the window size for n points: roughly ln(n), which balances the
+/// n additions per window against the 2^c additions needed to sum the buckets.
+fn window_size(n: usize) -> usize {
+    if n < 32 {
+        3
+    } else {
+        ((n as f64).ln().ceil() as usize + 2).min(16)
+    }
+}
+
+/// Computes the sum of scalars[i] * points[i] with Pippenger's bucket method.
+pub fn msm<E: EllipticCurve>(
+    points: &[AffinePoint<E>],
+    scalars: &[<E::ScalarField as FiniteField>::T],
+) -> AffinePoint<E> {
+    assert_eq!(
+        points.len(),
+        scalars.len(),
+        "the number of points and scalars must match"
+    );
+
+    // negative scalars are handled by negating the point instead
+    let (points, scalars): (Vec<_>, Vec<_>) = points
+        .iter()
+        .zip(scalars)
+        .map(|(p, s)| {
+            let k = E::ScalarField::to_bigint(s);
+            if k.is_negative() {
+                (-p.clone(), -k)
+            } else {
+                (p.clone(), k)
+            }
+        })
+        .filter(|(p, k)| !p.is_inf() && !k.is_zero())
+        .unzip();
+
+    let max_bits = scalars.iter().map(|k| k.bits()).max().unwrap_or(0);
+    if max_bits == 0 {
+        return AffinePoint::Infinity;
+    }
+
+    let c = window_size(points.len());
+    let windows = max_bits.div_ceil(c as u64);
+
+    let mut acc = JacobianPoint::<E>::infinity();
+    for w in (0..windows).rev() {
+        for _ in 0..c {
+            acc = acc.double();
+        }
+
+        // buckets[j - 1] holds the sum of the points whose digit is j
+        let mut buckets = vec![JacobianPoint::<E>::infinity(); (1 << c) - 1];
+        for (p, k) in points.iter().zip(&scalars) {
+            let digit = window_digit(k, w * c as u64, c);
+            if digit != 0 {
+                buckets[digit - 1] = buckets[digit - 1].add_mixed(p);
+            }
+        }
+
+        // sum_j j * bucket_j, as the sum of the running sums from the top
+        let mut running = JacobianPoint::<E>::infinity();
+        let mut window_sum = JacobianPoint::<E>::infinity();
+        for bucket in buckets.into_iter().rev() {
+            running = running + bucket;
+            window_sum = window_sum + running.clone();
+        }
+
+        acc = acc + window_sum;
+    }
+    acc.to_affine()
+}
+
+/// The c-bit digit of k starting at bit `offset`.
+fn window_digit(k: &BigInt, offset: u64, c: usize) -> usize {
+    (0..c as u64)
+        .filter(|j| k.bit(offset + j))
+        .map(|j| 1 << j)
+        .sum()
+}
+
+/// The reference implementation: one double-and-add per point.
+pub fn msm_naive<E: EllipticCurve>(
+    points: &[AffinePoint<E>],
+    scalars: &[<E::ScalarField as FiniteField>::T],
+) -> AffinePoint<E> {
+    assert_eq!(
+        points.len(),
+        scalars.len(),
+        "the number of points and scalars must match"
+    );
+    points
+        .iter()
+        .zip(scalars)
+        .fold(AffinePoint::Infinity, |acc, (p, s)| {
+            acc + p.clone() * s.clone()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curves::{MoonMath, TinyJJ};
+
+    use super::*;
+
+    #[test]
+    fn test_msm_tinyjj() {
+        let g = TinyJJ::generator();
+        let points: Vec<_> = (1..40i16).map(|i| g.clone() * (7 * i + 3)).collect();
+        let scalars: Vec<i16> = (1..40i16).map(|i| (i * 739) % 28800 - 500).collect();
+
+        for n in [0, 1, 2, 5, 31, 32, 39] {
+            assert_eq!(
+                msm(&points[..n], &scalars[..n]),
+                msm_naive(&points[..n], &scalars[..n]),
+                "n = {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn test_msm_edge_cases() {
+        let g = TinyJJ::generator();
+        let points = vec![g.clone(), AffinePoint::Infinity, g.clone(), -g.clone()];
+
+        assert!(msm(&points, &[0, 5, 0, 0]).is_inf());
+        assert!(msm(&points, &[3, 1, 0, 3]).is_inf());
+        assert_eq!(msm(&points, &[1, 1, 1, 0]), g.clone() * 2);
+        assert_eq!(msm(&points, &[-1, 0, -1, 0]), -(g * 2));
+    }
+
+    #[test]
+    fn test_msm_moonmath() {
+        let g = MoonMath::generator();
+        let points: Vec<_> = (1..70).map(|i| g.clone() * (i * i + 1)).collect();
+        let scalars: Vec<i64> = (1..70).map(|i| i * 123456789 - 1000).collect();
+
+        assert_eq!(msm(&points, &scalars), msm_naive(&points, &scalars));
+    }
+
+    #[test]
+    fn test_window_size() {
+        assert_eq!(window_size(1), 3);
+        assert!(window_size(1000) > window_size(32));
+        assert!(window_size(usize::MAX) <= 16);
+    }
+}
@@ -0,0 +1,405 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+/// Any integer type that converts losslessly to `BigInt`, e.g. `u64`, `i64` or
+/// `BigInt` itself. All functions below do their arithmetic on `BigInt` and
+/// convert the result back, so they work the same for every such type.
+pub trait IntegerValue: Clone + Into<BigInt> + TryFrom<BigInt> {}
+
+impl<T> IntegerValue for T where T: Clone + Into<BigInt> + TryFrom<BigInt> {}
+
+fn big<N: IntegerValue>(n: &N) -> BigInt {
+    n.clone().into()
+}
+
+fn back<N: IntegerValue>(n: BigInt) -> N {
+    N::try_from(n)
+        .ok()
+        .expect("result does not fit in the integer type")
+}
+
+/// Greatest common divisor, always non-negative.
+pub fn gcd<N: IntegerValue>(a: &N, b: &N) -> N {
+    back(big(a).gcd(&big(b)))
+}
+
+/// Extended Euclidean algorithm: returns `(g, x, y)` with `a*x + b*y = g = gcd(a, b)`.
+/// The Bézout coefficients can be negative, so they are always `BigInt`s.
+pub fn xgcd<N: IntegerValue>(a: &N, b: &N) -> (N, BigInt, BigInt) {
+    let (mut r0, mut r1) = (big(a), big(b));
+    let (mut s0, mut s1) = (BigInt::one(), BigInt::zero());
+    let (mut t0, mut t1) = (BigInt::zero(), BigInt::one());
+
+    while !r1.is_zero() {
+        let quotient = &r0 / &r1;
+
+        let r2 = &r0 - &quotient * &r1;
+        r0 = std::mem::replace(&mut r1, r2);
+
+        let s2 = &s0 - &quotient * &s1;
+        s0 = std::mem::replace(&mut s1, s2);
+
+        let t2 = &t0 - &quotient * &t1;
+        t0 = std::mem::replace(&mut t1, t2);
+    }
+
+    if r0.is_negative() {
+        (back(-r0), -s0, -t0)
+    } else {
+        (back(r0), s0, t0)
+    }
+}
+
+/// Computes `base^exp mod m`.
+pub fn mod_pow<N: IntegerValue>(base: &N, exp: &N, m: &N) -> N {
+    let m = big(m);
+    back(big(base).mod_floor(&m).modpow(&big(exp), &m))
+}
+
+/// Returns the inverse of `a` modulo `m`, if `a` and `m` are coprime.
+pub fn mod_inverse<N: IntegerValue>(a: &N, m: &N) -> Option<N> {
+    let m = big(m);
+    let (g, x, _) = xgcd(&big(a), &m);
+    if g.is_one() {
+        Some(back(x.mod_floor(&m)))
+    } else {
+        None
+    }
+}
+
+/// Chinese remainder theorem: finds `x` with `x ≡ a_i mod m_i` for every
+/// pair `(a_i, m_i)`. The moduli need not be coprime. Returns `(x, lcm(m_i))`,
+/// or `None` if the congruences are inconsistent.
+pub fn crt<N: IntegerValue>(congruences: &[(N, N)]) -> Option<(N, N)> {
+    let mut x = BigInt::zero();
+    let mut m = BigInt::one();
+
+    for (a_i, m_i) in congruences {
+        let (a_i, m_i) = (big(a_i), big(m_i));
+        let (g, p, _) = xgcd(&m, &m_i);
+        let diff = &a_i - &x;
+        if !(&diff % &g).is_zero() {
+            return None;
+        }
+        // x + m * t ≡ a_i (mod m_i)  =>  t ≡ (diff / g) * p (mod m_i / g)
+        let step = &m_i / &g;
+        let t = (diff / &g * p).mod_floor(&step);
+        x += &m * t;
+        m *= step;
+        x = x.mod_floor(&m);
+    }
+
+    Some((back(x), back(m)))
+}
+
+const SMALL_ORDER_BOUND: u32 = 1 << 12;
+
+const SMALL_PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+/// Miller–Rabin primality test. Using the first 13 primes as witnesses makes
+/// the answer exact for every n < 3.3 * 10^24 (in particular for all `u64`)
+/// and overwhelmingly likely to be correct above that.
+pub fn is_prime<N: IntegerValue>(n: &N) -> bool {
+    let n = big(n);
+    let one = BigInt::one();
+    if n <= one {
+        return false;
+    }
+    for p in SMALL_PRIMES {
+        let p = BigInt::from(p);
+        if n == p {
+            return true;
+        }
+        if (&n % &p).is_zero() {
+            return false;
+        }
+    }
+
+    // write n - 1 = d * 2^s with d odd
+    let n_minus_one = &n - &one;
+    let s = n_minus_one.trailing_zeros().unwrap();
+    let d = &n_minus_one >> s;
+
+    'witness: for a in SMALL_PRIMES {
+        let mut x = BigInt::from(a).modpow(&d, &n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = &x * &x % &n;
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Pollard's rho with Brent's cycle detection. Returns a non-trivial divisor
+/// of a composite `n`, trying a new polynomial x^2 + c whenever the walk fails.
+fn pollard_rho(n: &BigInt) -> BigInt {
+    if n.is_even() {
+        return BigInt::from(2);
+    }
+
+    let one = BigInt::one();
+    let mut c = BigInt::one();
+    loop {
+        let f = |x: &BigInt| (x * x + &c) % n;
+        let (mut x, mut y) = (BigInt::from(2), BigInt::from(2));
+        let mut d = one.clone();
+        let mut power = 1u64;
+        let mut steps = 0u64;
+
+        while d == one {
+            if steps == power {
+                x = y.clone();
+                power *= 2;
+                steps = 0;
+            }
+            y = f(&y);
+            steps += 1;
+            d = (&x - &y).abs().gcd(n);
+        }
+
+        if &d != n {
+            return d;
+        }
+        c += 1;
+    }
+}
+
+/// Returns the prime factorization of `n` as `(prime, exponent)` pairs,
+/// sorted by prime. Small factors are removed by trial division, the
+/// rest is split with Pollard's rho.
+pub fn factor<N: IntegerValue>(n: &N) -> Vec<(N, u32)> {
+    let mut n = big(n).abs();
+    let mut primes = vec![];
+
+    if n.is_zero() {
+        return vec![];
+    }
+
+    // composite divisors never divide what is left, as their
+    // prime factors have already been removed
+    for p in 2u32..1000 {
+        let p = BigInt::from(p);
+        if &p * &p > n {
+            break;
+        }
+        while (&n % &p).is_zero() {
+            primes.push(p.clone());
+            n /= &p;
+        }
+    }
+
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m.is_one() {
+            continue;
+        }
+        if is_prime(&m) {
+            primes.push(m);
+            continue;
+        }
+        let d = pollard_rho(&m);
+        stack.push(&m / &d);
+        stack.push(d);
+    }
+
+    primes.sort();
+    let mut result: Vec<(BigInt, u32)> = vec![];
+    for p in primes {
+        match result.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => result.push((p, 1)),
+        }
+    }
+    result.into_iter().map(|(p, e)| (back(p), e)).collect()
+}
+
+/// Returns the largest prime factor of `n`.
+pub fn largest_prime_factor<N: IntegerValue>(n: &N) -> Option<N> {
+    factor(n).pop().map(|(p, _)| p)
+}
+
+/// Euler's totient function.
+pub fn euler_phi<N: IntegerValue>(n: &N) -> N {
+    let phi = factor(n)
+        .into_iter()
+        .map(|(p, e)| {
+            let p = big(&p);
+            p.pow(e - 1) * (p - 1)
+        })
+        .product();
+    back(phi)
+}
+
+/// Returns the smallest `k > 0` with `q^k ≡ 1 mod r`, or `None` if
+/// `q` is not invertible modulo `r`.
+///
+/// Small orders (such as embedding degrees) are found by simply trying
+/// k = 1, 2, ... Otherwise, as the order divides φ(r), we start from φ(r)
+/// and strip away every prime factor that still leaves a multiple of the order.
+pub fn multiplicative_order<N: IntegerValue>(q: &N, r: &N) -> Option<N> {
+    let (q, r) = (big(q), big(r));
+    if r.is_one() {
+        return Some(back(BigInt::one()));
+    }
+    if !q.gcd(&r).is_one() {
+        return None;
+    }
+
+    let q = q.mod_floor(&r);
+    let mut power = q.clone();
+    for k in 1..=SMALL_ORDER_BOUND {
+        if power.is_one() {
+            return Some(back(BigInt::from(k)));
+        }
+        power = power * &q % &r;
+    }
+
+    let mut order = euler_phi(&r);
+    for (p, _) in factor(&order) {
+        while (&order % &p).is_zero() && q.modpow(&(&order / &p), &r).is_one() {
+            order /= &p;
+        }
+    }
+    Some(back(order))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        curves::{MoonMath, TinyJJ},
+        elliptic_curve::EllipticCurve,
+        fields::{Ff103_12, Ff13, Ff13_2, Ff13_4, Ff43, Ff43_6, FfBn254, FfBn254_12, FfBn254_2},
+        finite_field::{FiniteField, NonExtendedField},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(&12u64, &18u64), 6);
+        assert_eq!(gcd(&17u64, &5u64), 1);
+        assert_eq!(gcd(&0u64, &5u64), 5);
+        assert_eq!(gcd(&BigInt::from(-12), &BigInt::from(18)), BigInt::from(6));
+    }
+
+    #[test]
+    fn test_xgcd() {
+        let (g, x, y) = xgcd(&240u64, &46u64);
+        assert_eq!(g, 2);
+        assert_eq!(x * 240 + y * 46, BigInt::from(2));
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(&7u64, &13u64), Some(2));
+        assert_eq!(mod_inverse(&6u64, &9u64), None);
+        assert_eq!(
+            mod_inverse(&BigInt::from(-3), &BigInt::from(43)),
+            Some(BigInt::from(14))
+        );
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(&3u64, &6u64, &13u64), 1);
+        assert_eq!(mod_pow(&2u64, &10u64, &1000u64), 24);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2u64, 3u64), (3, 5), (2, 7)]), Some((23, 105)));
+        // non-coprime but consistent moduli
+        assert_eq!(crt(&[(3u64, 4u64), (1, 6)]), Some((7, 12)));
+        // inconsistent
+        assert_eq!(crt(&[(0u64, 4u64), (1, 6)]), None);
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let primes: Vec<u64> = (0..60).filter(is_prime).collect();
+        assert_eq!(
+            primes,
+            vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59]
+        );
+        // Carmichael numbers fool the Fermat test, but not Miller–Rabin
+        assert!(!is_prime(&561u64));
+        assert!(!is_prime(&3215031751u64));
+        assert!(is_prime(&18446744073709551557u64));
+        assert!(!is_prime(&18446744073709551559u64));
+    }
+
+    #[test]
+    fn test_factor() {
+        assert_eq!(factor(&28800u64), vec![(2, 7), (3, 2), (5, 2)]);
+        assert_eq!(factor(&1u64), vec![]);
+        assert_eq!(factor(&97u64), vec![(97, 1)]);
+        // product of two primes far beyond trial division
+        assert_eq!(
+            factor(&(1000003u64 * 998244353)),
+            vec![(1000003, 1), (998244353, 1)]
+        );
+        assert_eq!(largest_prime_factor(&28800u64), Some(5));
+        assert_eq!(
+            factor(&6321251664u64),
+            vec![(2, 4), (3, 4), (7, 2), (13, 2), (19, 1), (31, 1)]
+        );
+    }
+
+    #[test]
+    fn test_euler_phi() {
+        assert_eq!(euler_phi(&13u64), 12);
+        assert_eq!(euler_phi(&36u64), 12);
+    }
+
+    #[test]
+    fn test_multiplicative_order() {
+        assert_eq!(multiplicative_order(&13u64, &5u64), Some(4));
+        assert_eq!(multiplicative_order(&43u64, &13u64), Some(6));
+        assert_eq!(multiplicative_order(&3u64, &13u64), Some(3));
+        assert_eq!(multiplicative_order(&2u64, &1u64), Some(1));
+        assert_eq!(multiplicative_order(&5u64, &10u64), None);
+    }
+
+    #[test]
+    fn test_bn254_embedding_degree() {
+        let q = FfBn254::to_bigint(&FfBn254::modulus());
+        let r = BigInt::from_str(
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+        )
+        .unwrap();
+        assert!(is_prime(&r));
+        assert_eq!(multiplicative_order(&q, &r), Some(BigInt::from(12)));
+    }
+
+    #[test]
+    fn test_prime_field_moduli() {
+        assert!(Ff13::is_prime_modulus());
+        assert!(Ff43::is_prime_modulus());
+        assert!(FfBn254::is_prime_modulus());
+    }
+
+    #[test]
+    fn test_extension_field_moduli() {
+        assert!(Ff13_2::is_valid_modulus());
+        assert!(Ff13_4::is_valid_modulus());
+        assert!(Ff43_6::is_valid_modulus());
+        assert!(Ff103_12::is_valid_modulus());
+        assert!(FfBn254_2::is_valid_modulus());
+        assert!(FfBn254_12::is_valid_modulus());
+    }
+
+    #[test]
+    fn test_curve_parameters() {
+        assert!(TinyJJ::has_valid_r());
+        assert!(MoonMath::has_valid_r());
+    }
+}
@@ -1,3 +1,4 @@
+use num_integer::Integer;
 use num_traits::Pow;
 
 use crate::{
@@ -5,6 +6,8 @@ use crate::{
     field_element::FieldElement,
     finite_field::{FiniteField, NonExtendedField},
     logger::{log_table_row, log_table_titles},
+    point_counting,
+    projective::JacobianPoint,
 };
 
 pub fn dist_relationship<E: EllipticCurve>(
@@ -30,6 +33,54 @@ pub fn dist_relationship<E: EllipticCurve>(
     }
 }
 
+/// Same as `dist_relationship(t, t, q)` with `t` in Jacobian coordinates.
+/// To avoid the division in the slope, the value is scaled by 2*Y*Z^3
+/// (by Z^2 for a vertical line). When `t` has coordinates in the base
+/// prime field, the final exponentiation wipes out that factor.
+pub fn dist_relationship_double<E: EllipticCurve>(
+    t: &JacobianPoint<E>,
+    q: &AffinePoint<E>,
+) -> FieldElement<E::BaseField> {
+    let (xq, yq) = q.xy().unwrap();
+    let zz = t.z.clone() * t.z.clone();
+
+    if t.y.is_zero() {
+        return xq * zz - t.x.clone();
+    }
+
+    // (3 * X^2 + a * Z^4) * (xq * Z^2 - X) - 2 * Y * (yq * Z^3 - Y)
+    let x_pow_2 = t.x.clone() * t.x.clone();
+    let m = x_pow_2.clone() + x_pow_2.clone() + x_pow_2 + E::a() * zz.clone() * zz.clone();
+    let two_y = t.y.clone() + t.y.clone();
+    m * (xq * zz.clone() - t.x.clone()) - two_y * (yq * zz * t.z.clone() - t.y.clone())
+}
+
+/// Same as `dist_relationship(t, p, q)` with `t` in Jacobian coordinates,
+/// scaled by H*Z where H = xp * Z^2 - X (by 2*yp for a tangent line).
+pub fn dist_relationship_add<E: EllipticCurve>(
+    t: &JacobianPoint<E>,
+    p: &AffinePoint<E>,
+    q: &AffinePoint<E>,
+) -> FieldElement<E::BaseField> {
+    let (xp, yp) = p.xy().unwrap();
+    let (xq, yq) = q.xy().unwrap();
+    let zz = t.z.clone() * t.z.clone();
+
+    let h = xp.clone() * zz.clone() - t.x.clone();
+    let r = yp.clone() * zz * t.z.clone() - t.y.clone();
+
+    if !h.is_zero() {
+        r * (xq - xp) - h * t.z.clone() * (yq - yp)
+    } else if !r.is_zero() || yp.is_zero() {
+        xq - xp
+    } else {
+        // t == p, so the line is the tangent at p
+        let x_pow_2 = xp.clone() * xp.clone();
+        let m = x_pow_2.clone() + x_pow_2.clone() + x_pow_2 + E::a();
+        m * (xq - xp) - (yp.clone() + yp.clone()) * (yq - yp)
+    }
+}
+
 pub trait Pairing: EllipticCurve {
     fn is_valid_g1(p: &AffinePoint<Self>) -> bool {
         let k = Self::ScalarField::from_uint(Self::embedding_degree()).unwrap();
@@ -40,6 +91,20 @@ pub trait Pairing: EllipticCurve {
         q.trace_map().is_inf()
     }
 
+    /// The same test as `is_valid_g1` with a single Frobenius map instead of
+    /// the whole trace: G1 is made of the points fixed by π.
+    fn is_valid_g1_frobenius(p: &AffinePoint<Self>) -> bool {
+        p.frobenius(1) == *p
+    }
+
+    /// The same test as `is_valid_g2` for points of E[r]: π acts on G2 as
+    /// multiplication by q, so checking π(Q) = [q mod r]Q avoids the trace.
+    fn is_valid_g2_frobenius(q: &AffinePoint<Self>) -> bool {
+        let (size, _) = point_counting::field_of_definition::<Self>();
+        let r = Self::ScalarField::to_bigint(&Self::r());
+        q.frobenius(1) == q.mul_double_and_add(&(size % r))
+    }
+
     fn miller_loop(p: &AffinePoint<Self>, q: &AffinePoint<Self>) -> FieldElement<Self::BaseField> {
         // 1. It first carries out an implicit multiplication of 𝑃 by 𝑟, using
         // the standard double-and-add algorithm for point multiplication.
@@ -58,14 +123,14 @@ pub trait Pairing: EllipticCurve {
         let bits = Self::ScalarField::to_bits(Self::r());
         log_table_titles();
         for bit in bits.iter().skip(1) {
-            let f_new = dist_relationship(&point, &point, &q);
+            let f_new = dist_relationship(&point, &point, q);
             f = f.clone() * f * f_new.clone();
             point = point.clone().double();
 
             log_table_row(&(*bit as u8).to_string(), "double", &f_new, &f, &point);
 
             if *bit {
-                let f_new = dist_relationship(&point, &p, &q);
+                let f_new = dist_relationship(&point, p, q);
                 f = f * f_new.clone();
                 point = point + p.clone();
 
@@ -79,6 +144,35 @@ pub trait Pairing: EllipticCurve {
         f
     }
 
+    /// The same loop as `miller_loop`, but the accumulator point is kept in
+    /// Jacobian coordinates, so no field inversions are needed. The output
+    /// differs from `miller_loop` by a factor from the base prime field,
+    /// therefore `p` must be a G1 point and the result is only meaningful
+    /// after the final exponentiation.
+    fn miller_loop_jacobian(
+        p: &AffinePoint<Self>,
+        q: &AffinePoint<Self>,
+    ) -> FieldElement<Self::BaseField> {
+        let mut point = JacobianPoint::from(p.clone());
+        let mut f = FieldElement::<Self::BaseField>::one();
+
+        let bits = Self::ScalarField::to_bits(Self::r());
+        for bit in bits.iter().skip(1) {
+            let f_new = dist_relationship_double(&point, q);
+            f = f.clone() * f * f_new;
+            point = point.double();
+
+            if *bit {
+                let f_new = dist_relationship_add(&point, p, q);
+                f = f * f_new;
+                point = point.add_mixed(p);
+            }
+        }
+
+        assert!(point.is_inf());
+        f
+    }
+
     fn final_exponentiation(f: FieldElement<Self::BaseField>) -> FieldElement<Self::BaseField> {
         // The output value 𝑓 from the Miller loop must of the same order
         // as the points 𝑃 and 𝑄 which are of order 𝑟. That’s why we must
@@ -96,12 +190,93 @@ pub trait Pairing: EllipticCurve {
     }
 
     fn tate_pairing(p: &AffinePoint<Self>, q: &AffinePoint<Self>) -> FieldElement<Self::BaseField> {
+        Self::assert_valid_r();
         assert!(Self::is_valid_g1(p), "p is not a G1 point");
         assert!(Self::is_valid_g2(q), "q is not a G2 point");
 
         let f = Self::miller_loop(p, q);
         Self::final_exponentiation(f)
     }
+
+    /// Evaluates at `q` the Miller function f_{r,p} with divisor r(P) - r(O),
+    /// normalized so that its leading coefficient at infinity is 1. Unlike
+    /// `miller_loop`, every line is divided by the vertical line through the
+    /// next point, so the value is exact and not only up to the final
+    /// exponentiation. Returns None when one of the lines vanishes at `q`,
+    /// which only happens when `q` is a multiple of `p`.
+    fn miller_function(
+        p: &AffinePoint<Self>,
+        q: &AffinePoint<Self>,
+    ) -> Option<FieldElement<Self::BaseField>> {
+        let (xq, _) = q.xy().unwrap();
+        // dist_relationship gives -y + m * x + c for non-vertical lines
+        let line = |t: &AffinePoint<Self>, s: &AffinePoint<Self>| {
+            let value = dist_relationship(t, s, q);
+            if t.xy().unwrap().0 == s.xy().unwrap().0 && *t != *s {
+                value
+            } else {
+                -value
+            }
+        };
+        let vertical = |t: &AffinePoint<Self>| match t.xy() {
+            Some((x, _)) => xq.clone() - x,
+            None => FieldElement::one(),
+        };
+
+        let mut point = p.clone();
+        let mut f = FieldElement::<Self::BaseField>::one();
+
+        let bits = Self::ScalarField::to_bits(Self::r());
+        for bit in bits.iter().skip(1) {
+            let l = line(&point, &point);
+            point = point.clone().double();
+            let v = vertical(&point);
+            if l.is_zero() || v.is_zero() {
+                return None;
+            }
+            f = f.clone() * f * l / v;
+
+            if *bit {
+                let l = line(&point, p);
+                point = point + p.clone();
+                let v = vertical(&point);
+                if l.is_zero() || v.is_zero() {
+                    return None;
+                }
+                f = f * l / v;
+            }
+        }
+
+        assert!(point.is_inf());
+        Some(f)
+    }
+
+    /// The Weil pairing (-1)^r * f_{r,P}(Q) / f_{r,Q}(P) of two points of
+    /// E[r]. It needs no final exponentiation and no G1 or G2 inputs, but
+    /// costs two Miller loops with both points over the extension field.
+    fn weil_pairing(p: &AffinePoint<Self>, q: &AffinePoint<Self>) -> FieldElement<Self::BaseField> {
+        Self::assert_valid_r();
+        let r = Self::ScalarField::to_bigint(&Self::r());
+        assert!(
+            p.mul_double_and_add(&r).is_inf(),
+            "p is not an r-torsion point"
+        );
+        assert!(
+            q.mul_double_and_add(&r).is_inf(),
+            "q is not an r-torsion point"
+        );
+
+        if p.is_inf() || q.is_inf() || p == q {
+            return FieldElement::one();
+        }
+        // a line through multiples of one point vanishing at the other means
+        // they are linearly dependent, and the pairing is trivial
+        match (Self::miller_function(p, q), Self::miller_function(q, p)) {
+            (Some(f_p), Some(f_q)) if r.is_odd() => -(f_p / f_q),
+            (Some(f_p), Some(f_q)) => f_p / f_q,
+            _ => FieldElement::one(),
+        }
+    }
 }
 
 impl<T> Pairing for T where T: EllipticCurve {}
@@ -110,65 +285,111 @@ impl<T> Pairing for T where T: EllipticCurve {}
 mod tests {
     use super::*;
     use crate::{
-        curves::{TinyJJ, MoonMath}, elliptic_curve::EllipticCurve, fields::{Fe13_4, Fe43_6}, polynomial::Polynomial,
+        curves::{MoonMath, TinyJJ},
+        elliptic_curve::EllipticCurve,
+        fields::{Fe13_4, Fe43_6},
+        polynomial::Polynomial,
+        torsion::r_torsion,
     };
+    use num_bigint::BigInt;
 
     #[test]
     fn test_pairing_tinyjj_1() {
-        let p = AffinePoint::<TinyJJ>::new_xy(
+        let p = AffinePoint::<TinyJJ>::try_new_g1(
             Polynomial::from(vec![8]).into(),
             Polynomial::from(vec![8]).into(),
-        );
-        let q = AffinePoint::<TinyJJ>::new_xy(
+        )
+        .unwrap();
+        let q = AffinePoint::<TinyJJ>::try_new_g2(
             Polynomial::from(vec![7, 0, 4]).into(),
             Polynomial::from(vec![0, 10, 0, 5]).into(),
-        );
+        )
+        .unwrap();
         let result: Fe13_4 = Polynomial::from(vec![3, 7, 7, 6]).into();
         assert!(result == Pairing::tate_pairing(&p, &q));
     }
 
     #[test]
     fn test_pairing_tinyjj_2() {
-        let p = AffinePoint::<TinyJJ>::new_xy(
+        let p = AffinePoint::<TinyJJ>::try_new_g1(
             Polynomial::from(vec![8]).into(),
             Polynomial::from(vec![5]).into(),
-        );
-        let q = AffinePoint::<TinyJJ>::new_xy(
+        )
+        .unwrap();
+        let q = AffinePoint::<TinyJJ>::try_new_g2(
             Polynomial::from(vec![7, 0, 9]).into(),
             Polynomial::from(vec![0, 11, 0, 1]).into(),
-        );
+        )
+        .unwrap();
         let result: Fe13_4 = Polynomial::from(vec![3, 4, 6, 4]).into();
         assert!(result == Pairing::tate_pairing(&p, &q));
     }
 
     #[test]
     fn test_pairing_moonmath_1() {
-        let p = AffinePoint::<MoonMath>::new_xy(
+        let p = AffinePoint::<MoonMath>::try_new_g1(
             Polynomial::from(vec![27]).into(),
             Polynomial::from(vec![34]).into(),
-        );
+        )
+        .unwrap();
         // 10*v^2 : 28*v^3
-        let q = AffinePoint::<MoonMath>::new_xy(
+        let q = AffinePoint::<MoonMath>::try_new_g2(
             Polynomial::from(vec![0, 0, 10]).into(),
             Polynomial::from(vec![0, 0, 0, 28]).into(),
-        );
+        )
+        .unwrap();
         let result: Fe43_6 = Polynomial::from(vec![9, 4, 27, 12, 40, 21]).into();
         assert!(result == Pairing::tate_pairing(&p, &q));
     }
 
     #[test]
     fn test_pairing_bilinearity() {
-        let p = AffinePoint::<TinyJJ>::new_xy(
+        let p = AffinePoint::<TinyJJ>::try_new_g1(
             Polynomial::from(vec![8]).into(),
             Polynomial::from(vec![8]).into(),
-        );
-        let q = AffinePoint::<TinyJJ>::new_xy(
+        )
+        .unwrap();
+        let q = AffinePoint::<TinyJJ>::try_new_g2(
             Polynomial::from(vec![7, 0, 4]).into(),
             Polynomial::from(vec![0, 10, 0, 5]).into(),
-        );
+        )
+        .unwrap();
         assert!(Pairing::tate_pairing(&p, &q.double()) == Pairing::tate_pairing(&p.double(), &q));
     }
 
+    #[test]
+    fn test_pairing_miller_loop_jacobian() {
+        let p = AffinePoint::<TinyJJ>::try_new_g1(
+            Polynomial::from(vec![8]).into(),
+            Polynomial::from(vec![8]).into(),
+        )
+        .unwrap();
+        let q = AffinePoint::<TinyJJ>::try_new_g2(
+            Polynomial::from(vec![7, 0, 4]).into(),
+            Polynomial::from(vec![0, 10, 0, 5]).into(),
+        )
+        .unwrap();
+        let f = TinyJJ::miller_loop_jacobian(&p, &q);
+        assert_eq!(
+            TinyJJ::final_exponentiation(f),
+            Pairing::tate_pairing(&p, &q)
+        );
+
+        let p = AffinePoint::<MoonMath>::try_new_g1(
+            Polynomial::from(vec![27]).into(),
+            Polynomial::from(vec![34]).into(),
+        )
+        .unwrap();
+        let q = AffinePoint::<MoonMath>::try_new_g2(
+            Polynomial::from(vec![0, 0, 10]).into(),
+            Polynomial::from(vec![0, 0, 0, 28]).into(),
+        )
+        .unwrap();
+        let f = MoonMath::miller_loop_jacobian(&p, &q);
+        let result: Fe43_6 = Polynomial::from(vec![9, 4, 27, 12, 40, 21]).into();
+        assert_eq!(MoonMath::final_exponentiation(f), result);
+    }
+
     #[test]
     fn test_pairing_dist_relationship() {
         let one = TinyJJ::generator();
@@ -189,4 +410,132 @@ mod tests {
         assert_ne!(dist_relationship(&one, &one, &two), Fe13_4::zero());
         assert_eq!(dist_relationship(&one, &one, &negtwo), Fe13_4::zero());
     }
+
+    #[test]
+    fn test_subgroup_checks_frobenius() {
+        fn assert_same_checks<E: Pairing>(step: usize) {
+            for p in r_torsion::<E>().into_iter().step_by(step) {
+                assert_eq!(E::is_valid_g1_frobenius(&p), E::is_valid_g1(&p));
+                assert_eq!(E::is_valid_g2_frobenius(&p), E::is_valid_g2(&p));
+            }
+        }
+        assert_same_checks::<TinyJJ>(1);
+        assert_same_checks::<MoonMath>(5);
+    }
+
+    fn tinyjj_points() -> (AffinePoint<TinyJJ>, AffinePoint<TinyJJ>) {
+        let p = AffinePoint::<TinyJJ>::try_new_g1(
+            Polynomial::from(vec![8]).into(),
+            Polynomial::from(vec![8]).into(),
+        )
+        .unwrap();
+        let q = AffinePoint::<TinyJJ>::try_new_g2(
+            Polynomial::from(vec![7, 0, 4]).into(),
+            Polynomial::from(vec![0, 10, 0, 5]).into(),
+        )
+        .unwrap();
+        (p, q)
+    }
+
+    fn moonmath_points() -> (AffinePoint<MoonMath>, AffinePoint<MoonMath>) {
+        let p = AffinePoint::<MoonMath>::try_new_g1(
+            Polynomial::from(vec![27]).into(),
+            Polynomial::from(vec![34]).into(),
+        )
+        .unwrap();
+        let q = AffinePoint::<MoonMath>::try_new_g2(
+            Polynomial::from(vec![0, 0, 10]).into(),
+            Polynomial::from(vec![0, 0, 0, 28]).into(),
+        )
+        .unwrap();
+        (p, q)
+    }
+
+    fn assert_weil_bilinear<E: Pairing>(p: &AffinePoint<E>, q: &AffinePoint<E>) {
+        let e = E::weil_pairing(p, q);
+        assert_ne!(e, FieldElement::one());
+        assert_eq!(
+            e.pow_bigint(&E::ScalarField::to_bigint(&E::r())),
+            FieldElement::one()
+        );
+
+        let two = BigInt::from(2);
+        let three = BigInt::from(3);
+        let e2 = e.pow_bigint(&two);
+        assert_eq!(E::weil_pairing(&p.mul_double_and_add(&two), q), e2);
+        assert_eq!(E::weil_pairing(p, &q.mul_double_and_add(&two)), e2);
+        assert_eq!(
+            E::weil_pairing(&p.mul_double_and_add(&two), &q.mul_double_and_add(&three)),
+            e.pow_bigint(&BigInt::from(6))
+        );
+
+        // points of E[r] outside G1 and G2
+        let r1 = p.clone() + q.clone();
+        let r2 = p.mul_double_and_add(&two) + q.mul_double_and_add(&three);
+        assert_eq!(
+            E::weil_pairing(&(r1.clone() + r2.clone()), q),
+            E::weil_pairing(&r1, q) * E::weil_pairing(&r2, q)
+        );
+    }
+
+    fn assert_weil_alternating<E: Pairing>(p: &AffinePoint<E>, q: &AffinePoint<E>) {
+        let r1 = p.clone() + q.clone();
+        let r2 = p.clone() + q.mul_double_and_add(&BigInt::from(3));
+        for r in [p, q, &r1, &r2] {
+            assert_eq!(E::weil_pairing(r, r), FieldElement::one());
+            assert_eq!(E::weil_pairing(r, &r.clone().double()), FieldElement::one());
+            assert_eq!(
+                E::weil_pairing(r, &r.mul_double_and_add(&BigInt::from(3))),
+                FieldElement::one()
+            );
+        }
+        assert_eq!(
+            E::weil_pairing(&r1, &r2) * E::weil_pairing(&r2, &r1),
+            FieldElement::one()
+        );
+        assert_eq!(
+            E::weil_pairing(p, q) * E::weil_pairing(q, p),
+            FieldElement::one()
+        );
+    }
+
+    fn assert_weil_tate<E: Pairing>(p: &AffinePoint<E>, q: &AffinePoint<E>) {
+        let tate = E::tate_pairing(p, q);
+        let f_p = E::miller_function(p, q).unwrap();
+        let f_q = E::miller_function(q, p).unwrap();
+        assert_eq!(E::final_exponentiation(f_p), tate);
+
+        // e_W^((q^k - 1) / r) = t(P, Q) / t(Q, P)
+        let (size, _) = point_counting::field_of_definition::<E>();
+        let r = E::ScalarField::to_bigint(&E::r());
+        let exponent = (size.pow(E::embedding_degree()) - 1u32) / &r;
+        assert_eq!(
+            E::weil_pairing(p, q).pow_bigint(&exponent),
+            tate / E::final_exponentiation(f_q)
+        );
+    }
+
+    #[test]
+    fn test_weil_pairing_bilinearity() {
+        let (p, q) = tinyjj_points();
+        assert_weil_bilinear(&p, &q);
+        let (p, q) = moonmath_points();
+        assert_weil_bilinear(&p, &q);
+    }
+
+    #[test]
+    fn test_weil_pairing_alternating() {
+        let (p, q) = tinyjj_points();
+        assert_weil_alternating(&p, &q);
+        let (p, q) = moonmath_points();
+        assert_weil_alternating(&p, &q);
+    }
+
+    #[test]
+    fn test_weil_pairing_tate() {
+        let (p, q) = tinyjj_points();
+        assert_weil_tate(&p, &q);
+        let (p, q) = moonmath_points();
+        assert_weil_tate(&p, &q);
+    }
 }
@@ -0,0 +1,425 @@
+use std::collections::HashMap;
+
+use num_bigint::BigInt;
+use num_integer::{Integer, Roots};
+use num_traits::{One, Pow, Signed, Zero};
+
+use crate::{
+    elliptic_curve::EllipticCurve,
+    field_element::FieldElement,
+    finite_field::{FiniteField, NonExtendedField},
+    number_theory::factor,
+    schoof::schoof_prime_subfield,
+};
+
+// Counting the points of y^2 = x^3 + ax + b over a finite field F_q.
+//
+// For tiny fields we simply enumerate all x and count the square roots of
+// the right-hand side. For larger fields we use Mestre's baby-step giant-step
+// method: by Hasse's theorem the order lies in [q + 1 - 2√q, q + 1 + 2√q], so
+// it suffices to find the multiples of the orders of a few points in that
+// interval, using the quadratic twist when the curve alone is ambiguous.
+//
+// Once #E(F_q) is known, the trace of Frobenius t = q + 1 - #E(F_q) gives the
+// order over every extension F_{q^k} without counting again.
+//
+// The curves here are given at runtime by their coefficients, since counting
+// also happens on twists and on curves over subfields.
+
+/// Fields up to this size are counted by enumeration.
+const NAIVE_BOUND: u32 = 1000;
+
+/// Prime fields up to this size are counted with baby-step giant-step, and
+/// larger ones with Schoof's algorithm.
+const BSGS_BOUND: u64 = 1 << 40;
+
+/// Points of the curve as optional coordinates, None being the point at infinity.
+type Point<M> = Option<(FieldElement<M>, FieldElement<M>)>;
+
+fn rhs<M: FiniteField>(
+    a: &FieldElement<M>,
+    b: &FieldElement<M>,
+    x: &FieldElement<M>,
+) -> FieldElement<M> {
+    x.clone() * x.clone() * x.clone() + a.clone() * x.clone() + b.clone()
+}
+
+fn add<M: FiniteField>(a: &FieldElement<M>, p: &Point<M>, q: &Point<M>) -> Point<M> {
+    let (x1, y1) = match p {
+        None => return q.clone(),
+        Some(p) => p.clone(),
+    };
+    let (x2, y2) = match q {
+        None => return p.clone(),
+        Some(q) => q.clone(),
+    };
+
+    let slope = if x1 == x2 {
+        if y1 != y2 || y1.is_zero() {
+            return None;
+        }
+        let three = FieldElement::new(M::from_coefficients(&[BigInt::from(3)]));
+        (three * x1.clone() * x1.clone() + a.clone()) / (y1.clone() + y1.clone())
+    } else {
+        (y2 - y1.clone()) / (x2.clone() - x1.clone())
+    };
+    let x3 = slope.clone() * slope.clone() - x1.clone() - x2;
+    let y3 = slope * (x1 - x3.clone()) - y1;
+    Some((x3, y3))
+}
+
+fn neg<M: FiniteField>(p: &Point<M>) -> Point<M> {
+    p.clone().map(|(x, y)| (x, -y))
+}
+
+fn mul<M: FiniteField>(a: &FieldElement<M>, p: &Point<M>, k: &BigInt) -> Point<M> {
+    let base = if k.is_negative() { neg(p) } else { p.clone() };
+    let k = k.magnitude();
+    let mut acc = None;
+    for i in (0..k.bits()).rev() {
+        acc = add(a, &acc, &acc);
+        if k.bit(i) {
+            acc = add(a, &acc, &base);
+        }
+    }
+    acc
+}
+
+/// A hashable key for a point, for the baby-step table.
+fn key<M: FiniteField>(p: &Point<M>) -> Vec<u8> {
+    match p {
+        None => vec![],
+        Some((x, y)) => [x.to_bytes(), y.to_bytes()].concat(),
+    }
+}
+
+/// The Hasse interval [q + 1 - 2√q, q + 1 + 2√q] for a field of size q.
+pub fn hasse_interval(q: &BigInt) -> (BigInt, BigInt) {
+    // floor(2√q) = floor(√(4q))
+    let width = (q * 4u32).sqrt();
+    (q + 1u32 - &width, q + 1u32 + width)
+}
+
+/// The trace of Frobenius t = q + 1 - #E(F_q).
+pub fn frobenius_trace(q: &BigInt, order: &BigInt) -> BigInt {
+    q + 1u32 - order
+}
+
+/// Computes #E(F_{q^k}) = q^k + 1 - s_k, where s_0 = 2, s_1 = t and
+/// s_{i+1} = t s_i - q s_{i-1} are the sums of the k-th powers of the
+/// roots of the characteristic polynomial of Frobenius x^2 - tx + q.
+pub fn order_over_extension(q: &BigInt, trace: &BigInt, k: usize) -> BigInt {
+    let mut s_prev = BigInt::from(2);
+    let mut s = trace.clone();
+    for _ in 1..k {
+        let next = trace * &s - q * &s_prev;
+        s_prev = s;
+        s = next;
+    }
+    if k == 0 {
+        s = s_prev;
+    }
+    Pow::pow(q, k) + 1u32 - s
+}
+
+/// Counts the points of y^2 = x^3 + ax + b over the field M, including the
+/// point at infinity, by enumerating all x coordinates.
+pub fn count_points_naive<M: FiniteField>(a: &FieldElement<M>, b: &FieldElement<M>) -> BigInt {
+    let mut count = BigInt::one();
+    for x in FieldElement::<M>::elements() {
+        let y2 = rhs(a, b, &x);
+        if y2.is_zero() {
+            count += 1u32;
+        } else if y2.is_square() {
+            count += 2u32;
+        }
+    }
+    count
+}
+
+/// Finds all m in [low, high] such that mP = O, with baby-step giant-step.
+fn multiples_in_interval<M: FiniteField>(
+    a: &FieldElement<M>,
+    p: &Point<M>,
+    low: &BigInt,
+    high: &BigInt,
+) -> Vec<BigInt> {
+    // m = low + i * s + j, with 0 <= j < s
+    let width: BigInt = high - low + 1u32;
+    let s = width.sqrt() + 1u32;
+
+    // baby steps: -jP for 0 <= j < s
+    let mut baby: HashMap<Vec<u8>, Vec<BigInt>> = HashMap::new();
+    let minus_p = neg(p);
+    let mut step = None;
+    let mut j = BigInt::zero();
+    while j < s {
+        baby.entry(key(&step)).or_default().push(j.clone());
+        step = add(a, &step, &minus_p);
+        j += 1u32;
+    }
+
+    // giant steps: (low + i * s)P
+    let giant = mul(a, p, &s);
+    let mut current = mul(a, p, low);
+    let mut found = vec![];
+    let mut i = BigInt::zero();
+    while &i * &s <= width {
+        if let Some(js) = baby.get(&key(&current)) {
+            for j in js {
+                let m = low + &i * &s + j;
+                if &m <= high {
+                    found.push(m);
+                }
+            }
+        }
+        current = add(a, &current, &giant);
+        i += 1u32;
+    }
+    found.sort();
+    found.dedup();
+    found
+}
+
+/// The order of P, given a multiple of it.
+fn order_from_multiple<M: FiniteField>(a: &FieldElement<M>, p: &Point<M>, m: &BigInt) -> BigInt {
+    let mut order = m.clone();
+    for (prime, _) in factor(m) {
+        while order.is_multiple_of(&prime) && mul(a, p, &(&order / &prime)).is_none() {
+            order /= &prime;
+        }
+    }
+    order
+}
+
+/// Points on the curve, found by trying x = 0, 1, 2, ... in order.
+fn points<M: FiniteField>(
+    a: FieldElement<M>,
+    b: FieldElement<M>,
+) -> impl Iterator<Item = Point<M>> {
+    FieldElement::<M>::elements().filter_map(move |x| {
+        rhs(&a, &b, &x)
+            .sqrt()
+            .filter(|y| !y.is_zero())
+            .map(|y| Some((x, y)))
+    })
+}
+
+/// Counts the points of y^2 = x^3 + ax + b over the field M with Mestre's
+/// baby-step giant-step algorithm. Intended for fields of more than a few
+/// hundred elements, where the order is always determined by a few points
+/// of the curve or of its quadratic twist.
+pub fn count_points_bsgs<M: FiniteField>(a: &FieldElement<M>, b: &FieldElement<M>) -> BigInt {
+    let q = M::size();
+    let (low, high) = hasse_interval(&q);
+    // #E + #E' = 2q + 2
+    let total: BigInt = &q * 2u32 + 2u32;
+    let (twist_low, twist_high) = (&total - &high, &total - &low);
+
+    // the twist y^2 = x^3 + a g^2 x + b g^3 by a non-square g
+    let g = FieldElement::<M>::non_square();
+    let twist_a = a.clone() * g.clone() * g.clone();
+    let twist_b = b.clone() * g.clone() * g.clone() * g;
+
+    let mut curve_points = points(a.clone(), b.clone());
+    let mut twist_points = points(twist_a.clone(), twist_b);
+    let mut curve_lcm = BigInt::one();
+    let mut twist_lcm = BigInt::one();
+
+    loop {
+        let p = curve_points.next();
+        let p_twist = twist_points.next();
+        assert!(
+            p.is_some() || p_twist.is_some(),
+            "could not determine the order of the curve"
+        );
+
+        if let Some(p) = p {
+            let m = &multiples_in_interval(a, &p, &low, &high)[0];
+            curve_lcm = curve_lcm.lcm(&order_from_multiple(a, &p, m));
+        }
+        if let Some(p) = p_twist {
+            let m = &multiples_in_interval(&twist_a, &p, &twist_low, &twist_high)[0];
+            twist_lcm = twist_lcm.lcm(&order_from_multiple(&twist_a, &p, m));
+        }
+
+        // the orders in the interval that are compatible with both curves
+        let mut candidate = low.div_ceil(&curve_lcm) * &curve_lcm;
+        let mut candidates = vec![];
+        while candidate <= high && candidates.len() < 2 {
+            if (&total - &candidate).is_multiple_of(&twist_lcm) {
+                candidates.push(candidate.clone());
+            }
+            candidate += &curve_lcm;
+        }
+        if candidates.len() == 1 {
+            return candidates.pop().unwrap();
+        }
+    }
+}
+
+/// Counts the points of y^2 = x^3 + ax + b over the field M.
+pub fn count_points<M: FiniteField>(a: &FieldElement<M>, b: &FieldElement<M>) -> BigInt {
+    if M::size() <= BigInt::from(NAIVE_BOUND) {
+        count_points_naive(a, b)
+    } else {
+        count_points_bsgs(a, b)
+    }
+}
+
+/// Counts the points of a curve whose coefficients lie in the prime subfield
+/// F_p of M, over F_p only, by enumeration.
+fn count_points_prime_subfield<M: FiniteField>(a: &FieldElement<M>, b: &FieldElement<M>) -> BigInt {
+    let p = M::characteristic();
+    let euler_exp: BigInt = (&p - 1u32) >> 1;
+
+    let mut count = BigInt::one();
+    let mut x = BigInt::zero();
+    while x < p {
+        let y2 = rhs(a, b, &FieldElement::from_index(&x));
+        if y2.is_zero() {
+            count += 1u32;
+        } else if y2.pow_bigint(&euler_exp) == FieldElement::one() {
+            count += 2u32;
+        }
+        x += 1u32;
+    }
+    count
+}
+
+/// The field F_q0 the curve is defined over, as its size q0 together with the
+/// degree m of the base field F_{q0^m} over it. This is the prime subfield
+/// when both coefficients lie in it, and the base field otherwise.
+pub fn field_of_definition<E: EllipticCurve>() -> (BigInt, usize) {
+    if E::a().is_in_prime_subfield() && E::b().is_in_prime_subfield() {
+        (E::BaseField::characteristic(), E::BaseField::degree())
+    } else {
+        (E::BaseField::size(), 1)
+    }
+}
+
+/// Computes #E(F_q0) over the field of definition of the curve. Small fields
+/// are enumerated and large prime fields go through Schoof's algorithm, with
+/// baby-step giant-step in between when F_q0 is the base field itself.
+/// Large fields F_{p^m} with m > 1 are not supported.
+pub fn definition_field_order<E: EllipticCurve>() -> BigInt {
+    let (a, b) = (E::a(), E::b());
+    let (q0, m) = field_of_definition::<E>();
+    if m == 1 && q0 <= BigInt::from(BSGS_BOUND) {
+        return count_points(&a, &b);
+    }
+    assert!(
+        q0 == E::BaseField::characteristic(),
+        "point counting over F_{{p^m}} for large p is unsupported"
+    );
+    if q0 <= BigInt::from(NAIVE_BOUND) {
+        count_points_prime_subfield(&a, &b)
+    } else {
+        let trace = schoof_prime_subfield(&a, &b);
+        &q0 + 1u32 - trace
+    }
+}
+
+/// Computes #E(F_q) for the base field F_q of the curve, counting the points
+/// over its field of definition F_q0 and lifting the order to F_q = F_{q0^m}
+/// through the trace of Frobenius.
+pub fn curve_order<E: EllipticCurve>() -> BigInt {
+    let (q0, m) = field_of_definition::<E>();
+    let order = definition_field_order::<E>();
+    let trace = frobenius_trace(&q0, &order);
+    order_over_extension(&q0, &trace, m)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{Bn254Twist, MoonMath, MoonMathFp, Tiny7, TinyBn, TinyBnTwist, TinyJJ},
+        fields::{Fe13, Fe13_2, Fe13_4, Fe43, Fe43_6},
+        polynomial::Polynomial,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_count_points_naive() {
+        // y^2 = x^3 + 8x + 8 over F_13 has 20 points
+        assert_eq!(
+            count_points_naive(&Fe13::new(8), &Fe13::new(8)),
+            BigInt::from(20)
+        );
+        // y^2 = x^3 + 6 over F_43 has 39 points
+        assert_eq!(
+            count_points_naive(&Fe43::new(0), &Fe43::new(6)),
+            BigInt::from(39)
+        );
+    }
+
+    #[test]
+    fn test_order_over_extension() {
+        let q = BigInt::from(13);
+        let t = frobenius_trace(&q, &BigInt::from(20));
+        assert_eq!(t, BigInt::from(-6));
+        assert_eq!(order_over_extension(&q, &t, 1), BigInt::from(20));
+        assert_eq!(order_over_extension(&q, &t, 4), BigInt::from(28800));
+
+        // compare with counting over F_13^2 directly
+        let a: Fe13_2 = Polynomial::from(vec![8]).into();
+        assert_eq!(order_over_extension(&q, &t, 2), count_points_naive(&a, &a));
+    }
+
+    #[test]
+    fn test_hasse_interval() {
+        let (low, high) = hasse_interval(&BigInt::from(13));
+        assert_eq!((low, high), (BigInt::from(7), BigInt::from(21)));
+    }
+
+    #[test]
+    fn test_count_points_bsgs() {
+        let a: Fe13_4 = Polynomial::from(vec![8]).into();
+        assert_eq!(count_points_bsgs(&a, &a), BigInt::from(28800));
+
+        let a = Fe43_6::zero();
+        let b: Fe43_6 = Polynomial::from(vec![6]).into();
+        assert_eq!(count_points_bsgs(&a, &b), BigInt::from(6321251664i64));
+
+        // a curve that is not defined over the prime subfield
+        let a: Fe13_2 = Polynomial::from(vec![1, 2]).into();
+        let b: Fe13_2 = Polynomial::from(vec![0, 3]).into();
+        assert_eq!(count_points_bsgs(&a, &b), count_points_naive(&a, &b));
+    }
+
+    #[test]
+    #[should_panic(expected = "point counting over F_{p^m} for large p is unsupported")]
+    fn test_definition_field_order_large_extension() {
+        // the twist is defined over F_p^2 only, too large for BSGS
+        definition_field_order::<Bn254Twist>();
+    }
+
+    #[test]
+    fn test_shipped_curve_orders() {
+        assert_eq!(
+            curve_order::<TinyJJ>(),
+            <TinyJJ as EllipticCurve>::ScalarField::to_bigint(&TinyJJ::order())
+        );
+        assert_eq!(
+            curve_order::<MoonMath>(),
+            <MoonMath as EllipticCurve>::ScalarField::to_bigint(&MoonMath::order())
+        );
+        assert_eq!(
+            curve_order::<MoonMathFp>(),
+            <MoonMathFp as EllipticCurve>::ScalarField::to_bigint(&MoonMathFp::order())
+        );
+        assert_eq!(
+            curve_order::<Tiny7>(),
+            <Tiny7 as EllipticCurve>::ScalarField::to_bigint(&Tiny7::order())
+        );
+        assert_eq!(
+            curve_order::<TinyBn>(),
+            <TinyBn as EllipticCurve>::ScalarField::to_bigint(&TinyBn::order())
+        );
+        assert_eq!(
+            curve_order::<TinyBnTwist>(),
+            <TinyBnTwist as EllipticCurve>::ScalarField::to_bigint(&TinyBnTwist::order())
+        );
+    }
+}
@@ -1,8 +1,11 @@
 use std::fmt::{self, Display};
 use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
+use num_bigint::BigInt;
+
 use crate::field_element::FieldElement;
 use crate::finite_field::FiniteField;
+use crate::number_theory::factor;
 
 pub trait Coefficient:
     Clone
@@ -143,9 +146,9 @@ impl<M: FiniteField> From<Vec<M::T>> for Polynomial<FieldElement<M>> {
     }
 }
 
-impl<A: Coefficient, M: FiniteField<T = Self>> Into<FieldElement<M>> for Polynomial<A> {
-    fn into(self) -> FieldElement<M> {
-        FieldElement::<M>::new(self)
+impl<A: Coefficient, M: FiniteField<T = Polynomial<A>>> From<Polynomial<A>> for FieldElement<M> {
+    fn from(value: Polynomial<A>) -> Self {
+        FieldElement::<M>::new(value)
     }
 }
 
@@ -239,6 +242,50 @@ impl<T: Coefficient> Rem for Polynomial<T> {
     }
 }
 
+/// Rabin's irreducibility test: f of degree n over F_q is irreducible
+/// exactly when it divides x^(q^n) - x and is coprime to x^(q^(n/d)) - x
+/// for every prime d dividing n.
+pub fn is_irreducible<M: FiniteField>(f: &Polynomial<FieldElement<M>>) -> bool {
+    let n = f.degree();
+    if n == 0 {
+        return false;
+    }
+
+    let modulo = |p: Polynomial<FieldElement<M>>| p % f.clone();
+    let pow = |base: &Polynomial<FieldElement<M>>, exp: &BigInt| {
+        let mut result = Polynomial::new(vec![FieldElement::one()]);
+        for i in (0..exp.bits()).rev() {
+            result = modulo(result.clone() * result);
+            if exp.bit(i) {
+                result = modulo(result * base.clone());
+            }
+        }
+        result
+    };
+    let gcd = |a: &Polynomial<FieldElement<M>>, b: &Polynomial<FieldElement<M>>| {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        while !b.is_zero() {
+            let r = a % b.clone();
+            a = std::mem::replace(&mut b, r);
+        }
+        a
+    };
+
+    // x^(q^i) mod f for i = 0, ..., n
+    let x = Polynomial::new(vec![FieldElement::zero(), FieldElement::one()]);
+    let q = M::size();
+    let mut frobenius = vec![modulo(x.clone())];
+    for i in 1..=n {
+        frobenius.push(pow(&frobenius[i - 1], &q));
+    }
+
+    modulo(frobenius[n].clone() - x.clone()).is_zero()
+        && factor(&n).iter().all(|(d, _)| {
+            let g = gcd(f, &(frobenius[n / d].clone() - x.clone()));
+            g.degree() == 0
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{fields::Fe13, finite_field::FiniteField};
@@ -266,9 +313,9 @@ mod tests {
     #[test]
     fn test_polynomial_is_zero() {
         let p = Polynomial::new(vec![0, 0, 3, 0, 5]);
-        assert_eq!(p.is_zero(), false);
+        assert!(!p.is_zero());
         let p = Polynomial::new(vec![Fe13::zero()]);
-        assert_eq!(p.is_zero(), true);
+        assert!(p.is_zero());
     }
 
     #[test]
@@ -394,4 +441,18 @@ mod tests {
         assert_eq!(quotient.coefficients(), &[Fe13::new(7), Fe13::new(3)]);
         assert_eq!(remainder.coefficients(), &[Fe13::new(0)]);
     }
+
+    #[test]
+    fn test_polynomial_is_irreducible() {
+        // x^2 + 2 defines F_13^2, while x^2 + 1 = (x + 5)(x + 8)
+        let f: Polynomial<Fe13> = Polynomial::from(vec![2, 0, 1]);
+        assert!(is_irreducible(&f));
+        let f: Polynomial<Fe13> = Polynomial::from(vec![1, 0, 1]);
+        assert!(!is_irreducible(&f));
+        // no roots, but x^4 + 1 = (x^2 + 5)(x^2 + 8)
+        let f: Polynomial<Fe13> = Polynomial::from(vec![1, 0, 0, 0, 1]);
+        assert!(!is_irreducible(&f));
+        let f: Polynomial<Fe13> = Polynomial::from(vec![5]);
+        assert!(!is_irreducible(&f));
+    }
 }
@@ -0,0 +1,678 @@
+use std::ops::{Add, Mul, Neg};
+
+use num_traits::Signed;
+
+use crate::{
+    elliptic_curve::{AffinePoint, EllipticCurve},
+    field_element::FieldElement,
+    finite_field::{FiniteField, NonExtendedField},
+};
+
+// Inversion-free point arithmetic. Affine addition and doubling both divide
+// by a field element, which for extension fields means a full polynomial xgcd.
+// By carrying an extra coordinate Z we postpone all divisions until we
+// convert back to affine coordinates.
+//
+// The formulas follow the Explicit-Formulas Database
+// (https://hyperelliptic.org/EFD/g1p/auto-shortw.html).
+
+fn double_fe<M: FiniteField>(x: &FieldElement<M>) -> FieldElement<M> {
+    x.clone() + x.clone()
+}
+
+fn triple_fe<M: FiniteField>(x: &FieldElement<M>) -> FieldElement<M> {
+    double_fe(x) + x.clone()
+}
+
+fn square<M: FiniteField>(x: &FieldElement<M>) -> FieldElement<M> {
+    x.clone() * x.clone()
+}
+
+/// Inverts all elements with a single field inversion (Montgomery's trick).
+/// All elements must be non-zero.
+pub fn batch_inverse<M: FiniteField>(elements: &[FieldElement<M>]) -> Vec<FieldElement<M>> {
+    // prefix[i] = elements[0] * ... * elements[i - 1]
+    let mut prefix = Vec::with_capacity(elements.len());
+    let mut acc = FieldElement::one();
+    for e in elements {
+        prefix.push(acc.clone());
+        acc = acc * e.clone();
+    }
+
+    let mut inv = acc.inverse();
+    let mut result = vec![FieldElement::zero(); elements.len()];
+    for (i, e) in elements.iter().enumerate().rev() {
+        result[i] = inv.clone() * prefix[i].clone();
+        inv = inv * e.clone();
+    }
+    result
+}
+
+// ---------------- Projective ---------------------
+
+/// A point in homogeneous projective coordinates (X : Y : Z),
+/// representing the affine point (X/Z, Y/Z). The point at infinity is (0 : 1 : 0).
+#[derive(Debug, Clone)]
+pub struct ProjectivePoint<E: EllipticCurve> {
+    pub x: FieldElement<E::BaseField>,
+    pub y: FieldElement<E::BaseField>,
+    pub z: FieldElement<E::BaseField>,
+}
+
+impl<E: EllipticCurve> ProjectivePoint<E> {
+    pub fn new(
+        x: FieldElement<E::BaseField>,
+        y: FieldElement<E::BaseField>,
+        z: FieldElement<E::BaseField>,
+    ) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn infinity() -> Self {
+        Self::new(
+            FieldElement::zero(),
+            FieldElement::one(),
+            FieldElement::zero(),
+        )
+    }
+
+    pub fn is_inf(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    pub fn to_affine(&self) -> AffinePoint<E> {
+        if self.is_inf() {
+            return AffinePoint::Infinity;
+        }
+        let z_inv = self.z.inverse();
+        AffinePoint::XY(self.x.clone() * z_inv.clone(), self.y.clone() * z_inv)
+    }
+
+    /// Converts many points to affine coordinates using a single inversion.
+    pub fn batch_normalize(points: &[Self]) -> Vec<AffinePoint<E>> {
+        let finite: Vec<_> = points.iter().filter(|p| !p.is_inf()).collect();
+        let z_invs = batch_inverse(&finite.iter().map(|p| p.z.clone()).collect::<Vec<_>>());
+
+        let mut z_invs = z_invs.into_iter();
+        points
+            .iter()
+            .map(|p| {
+                if p.is_inf() {
+                    return AffinePoint::Infinity;
+                }
+                let z_inv = z_invs.next().unwrap();
+                AffinePoint::XY(p.x.clone() * z_inv.clone(), p.y.clone() * z_inv)
+            })
+            .collect()
+    }
+
+    /// dbl-2007-bl, skipping the a * Z^2 term when a = 0.
+    pub fn double(&self) -> Self {
+        if self.is_inf() || self.y.is_zero() {
+            return Self::infinity();
+        }
+
+        let xx = square(&self.x);
+        let w = if E::a().is_zero() {
+            triple_fe(&xx)
+        } else {
+            E::a() * square(&self.z) + triple_fe(&xx)
+        };
+        let s = double_fe(&(self.y.clone() * self.z.clone()));
+        let ss = square(&s);
+        let sss = s.clone() * ss;
+        let r = self.y.clone() * s.clone();
+        let rr = square(&r);
+        let b = square(&(self.x.clone() + r)) - xx - rr.clone();
+        let h = square(&w) - double_fe(&b);
+
+        let x3 = h.clone() * s;
+        let y3 = w * (b - h) - double_fe(&rr);
+        Self::new(x3, y3, sss)
+    }
+
+    /// add-1998-cmo-2
+    fn add_projective(&self, other: &Self) -> Self {
+        if self.is_inf() {
+            return other.clone();
+        }
+        if other.is_inf() {
+            return self.clone();
+        }
+
+        let y1z2 = self.y.clone() * other.z.clone();
+        let x1z2 = self.x.clone() * other.z.clone();
+        let z1z2 = self.z.clone() * other.z.clone();
+        let u = other.y.clone() * self.z.clone() - y1z2.clone();
+        let v = other.x.clone() * self.z.clone() - x1z2.clone();
+
+        if v.is_zero() {
+            return if u.is_zero() {
+                self.double()
+            } else {
+                Self::infinity()
+            };
+        }
+
+        let uu = square(&u);
+        let vv = square(&v);
+        let vvv = v.clone() * vv.clone();
+        let r = vv * x1z2;
+        let a = uu * z1z2.clone() - vvv.clone() - double_fe(&r);
+
+        let x3 = v * a.clone();
+        let y3 = u * (r - a) - vvv.clone() * y1z2;
+        let z3 = vvv * z1z2;
+        Self::new(x3, y3, z3)
+    }
+
+    /// madd-1998-cmo: adds an affine point (Z2 = 1), saving a few multiplications.
+    pub fn add_mixed(&self, other: &AffinePoint<E>) -> Self {
+        let (x2, y2) = match other {
+            AffinePoint::Infinity => return self.clone(),
+            AffinePoint::XY(x2, y2) => (x2, y2),
+        };
+        if self.is_inf() {
+            return other.clone().into();
+        }
+
+        let u = y2.clone() * self.z.clone() - self.y.clone();
+        let v = x2.clone() * self.z.clone() - self.x.clone();
+
+        if v.is_zero() {
+            return if u.is_zero() {
+                self.double()
+            } else {
+                Self::infinity()
+            };
+        }
+
+        let uu = square(&u);
+        let vv = square(&v);
+        let vvv = v.clone() * vv.clone();
+        let r = vv * self.x.clone();
+        let a = uu * self.z.clone() - vvv.clone() - double_fe(&r);
+
+        let x3 = v * a.clone();
+        let y3 = u * (r - a) - vvv.clone() * self.y.clone();
+        let z3 = vvv * self.z.clone();
+        Self::new(x3, y3, z3)
+    }
+}
+
+impl<E: EllipticCurve> From<AffinePoint<E>> for ProjectivePoint<E> {
+    fn from(p: AffinePoint<E>) -> Self {
+        match p {
+            AffinePoint::XY(x, y) => Self::new(x, y, FieldElement::one()),
+            AffinePoint::Infinity => Self::infinity(),
+        }
+    }
+}
+
+impl<E: EllipticCurve> From<ProjectivePoint<E>> for AffinePoint<E> {
+    fn from(p: ProjectivePoint<E>) -> Self {
+        p.to_affine()
+    }
+}
+
+/// Two triples represent the same point if they differ by a factor of Z.
+impl<E: EllipticCurve> PartialEq for ProjectivePoint<E> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.is_inf(), other.is_inf()) {
+            (true, true) => true,
+            (false, false) => {
+                self.x.clone() * other.z.clone() == other.x.clone() * self.z.clone()
+                    && self.y.clone() * other.z.clone() == other.y.clone() * self.z.clone()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<E: EllipticCurve> Add for ProjectivePoint<E> {
+    type Output = ProjectivePoint<E>;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.add_projective(&other)
+    }
+}
+
+impl<E: EllipticCurve> Add<AffinePoint<E>> for ProjectivePoint<E> {
+    type Output = ProjectivePoint<E>;
+
+    fn add(self, other: AffinePoint<E>) -> Self::Output {
+        self.add_mixed(&other)
+    }
+}
+
+impl<E: EllipticCurve> Neg for ProjectivePoint<E> {
+    type Output = ProjectivePoint<E>;
+
+    fn neg(self) -> Self::Output {
+        Self::new(self.x, -self.y, self.z)
+    }
+}
+
+/// Multiplication by a scalar
+impl<E: EllipticCurve> Mul<<E::ScalarField as FiniteField>::T> for ProjectivePoint<E> {
+    type Output = ProjectivePoint<E>;
+
+    fn mul(self, scalar: <E::ScalarField as FiniteField>::T) -> Self::Output {
+        let k = E::ScalarField::to_bigint(&scalar);
+        let magnitude = k.magnitude();
+
+        // Double-and-Add algorithm
+        let mut point = Self::infinity();
+        for i in (0..magnitude.bits()).rev() {
+            point = point.double();
+
+            if magnitude.bit(i) {
+                point = point + self.clone();
+            }
+        }
+
+        if k.is_negative() {
+            -point
+        } else {
+            point
+        }
+    }
+}
+
+// ---------------- Jacobian ---------------------
+
+/// A point in Jacobian coordinates (X : Y : Z), representing the affine
+/// point (X/Z^2, Y/Z^3). The point at infinity is (1 : 1 : 0).
+#[derive(Debug, Clone)]
+pub struct JacobianPoint<E: EllipticCurve> {
+    pub x: FieldElement<E::BaseField>,
+    pub y: FieldElement<E::BaseField>,
+    pub z: FieldElement<E::BaseField>,
+}
+
+impl<E: EllipticCurve> JacobianPoint<E> {
+    pub fn new(
+        x: FieldElement<E::BaseField>,
+        y: FieldElement<E::BaseField>,
+        z: FieldElement<E::BaseField>,
+    ) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn infinity() -> Self {
+        Self::new(
+            FieldElement::one(),
+            FieldElement::one(),
+            FieldElement::zero(),
+        )
+    }
+
+    pub fn is_inf(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    pub fn to_affine(&self) -> AffinePoint<E> {
+        if self.is_inf() {
+            return AffinePoint::Infinity;
+        }
+        let z_inv = self.z.inverse();
+        let z_inv_2 = square(&z_inv);
+        let z_inv_3 = z_inv_2.clone() * z_inv;
+        AffinePoint::XY(self.x.clone() * z_inv_2, self.y.clone() * z_inv_3)
+    }
+
+    /// Converts many points to affine coordinates using a single inversion.
+    pub fn batch_normalize(points: &[Self]) -> Vec<AffinePoint<E>> {
+        let finite: Vec<_> = points.iter().filter(|p| !p.is_inf()).collect();
+        let z_invs = batch_inverse(&finite.iter().map(|p| p.z.clone()).collect::<Vec<_>>());
+
+        let mut z_invs = z_invs.into_iter();
+        points
+            .iter()
+            .map(|p| {
+                if p.is_inf() {
+                    return AffinePoint::Infinity;
+                }
+                let z_inv = z_invs.next().unwrap();
+                let z_inv_2 = square(&z_inv);
+                let z_inv_3 = z_inv_2.clone() * z_inv;
+                AffinePoint::XY(p.x.clone() * z_inv_2, p.y.clone() * z_inv_3)
+            })
+            .collect()
+    }
+
+    /// dbl-2009-l when a = 0, dbl-2007-bl otherwise.
+    pub fn double(&self) -> Self {
+        if self.is_inf() || self.y.is_zero() {
+            return Self::infinity();
+        }
+
+        if E::a().is_zero() {
+            let a = square(&self.x);
+            let b = square(&self.y);
+            let c = square(&b);
+            let d = double_fe(&(square(&(self.x.clone() + b)) - a.clone() - c.clone()));
+            let e = triple_fe(&a);
+            let f = square(&e);
+
+            let x3 = f - double_fe(&d);
+            let y3 = e * (d - x3.clone()) - double_fe(&double_fe(&double_fe(&c)));
+            let z3 = double_fe(&(self.y.clone() * self.z.clone()));
+            return Self::new(x3, y3, z3);
+        }
+
+        let xx = square(&self.x);
+        let yy = square(&self.y);
+        let yyyy = square(&yy);
+        let zz = square(&self.z);
+        let s = double_fe(&(square(&(self.x.clone() + yy.clone())) - xx.clone() - yyyy.clone()));
+        let m = triple_fe(&xx) + E::a() * square(&zz);
+        let t = square(&m) - double_fe(&s);
+
+        let y3 = m * (s - t.clone()) - double_fe(&double_fe(&double_fe(&yyyy)));
+        let z3 = square(&(self.y.clone() + self.z.clone())) - yy - zz;
+        Self::new(t, y3, z3)
+    }
+
+    /// add-2007-bl
+    fn add_jacobian(&self, other: &Self) -> Self {
+        if self.is_inf() {
+            return other.clone();
+        }
+        if other.is_inf() {
+            return self.clone();
+        }
+
+        let z1z1 = square(&self.z);
+        let z2z2 = square(&other.z);
+        let u1 = self.x.clone() * z2z2.clone();
+        let u2 = other.x.clone() * z1z1.clone();
+        let s1 = self.y.clone() * other.z.clone() * z2z2.clone();
+        let s2 = other.y.clone() * self.z.clone() * z1z1.clone();
+        let h = u2 - u1.clone();
+        let r = double_fe(&(s2 - s1.clone()));
+
+        if h.is_zero() {
+            return if r.is_zero() {
+                self.double()
+            } else {
+                Self::infinity()
+            };
+        }
+
+        let i = square(&double_fe(&h));
+        let j = h.clone() * i.clone();
+        let v = u1 * i;
+
+        let x3 = square(&r) - j.clone() - double_fe(&v);
+        let y3 = r * (v - x3.clone()) - double_fe(&(s1 * j));
+        let z3 = (square(&(self.z.clone() + other.z.clone())) - z1z1 - z2z2) * h;
+        Self::new(x3, y3, z3)
+    }
+
+    /// madd-2007-bl: adds an affine point (Z2 = 1), saving a few multiplications.
+    pub fn add_mixed(&self, other: &AffinePoint<E>) -> Self {
+        let (x2, y2) = match other {
+            AffinePoint::Infinity => return self.clone(),
+            AffinePoint::XY(x2, y2) => (x2, y2),
+        };
+        if self.is_inf() {
+            return other.clone().into();
+        }
+
+        let z1z1 = square(&self.z);
+        let u2 = x2.clone() * z1z1.clone();
+        let s2 = y2.clone() * self.z.clone() * z1z1.clone();
+        let h = u2 - self.x.clone();
+        let r = double_fe(&(s2 - self.y.clone()));
+
+        if h.is_zero() {
+            return if r.is_zero() {
+                self.double()
+            } else {
+                Self::infinity()
+            };
+        }
+
+        let hh = square(&h);
+        let i = double_fe(&double_fe(&hh));
+        let j = h.clone() * i.clone();
+        let v = self.x.clone() * i;
+
+        let x3 = square(&r) - j.clone() - double_fe(&v);
+        let y3 = r * (v - x3.clone()) - double_fe(&(self.y.clone() * j));
+        let z3 = square(&(self.z.clone() + h)) - z1z1 - hh;
+        Self::new(x3, y3, z3)
+    }
+}
+
+impl<E: EllipticCurve> From<AffinePoint<E>> for JacobianPoint<E> {
+    fn from(p: AffinePoint<E>) -> Self {
+        match p {
+            AffinePoint::XY(x, y) => Self::new(x, y, FieldElement::one()),
+            AffinePoint::Infinity => Self::infinity(),
+        }
+    }
+}
+
+impl<E: EllipticCurve> From<JacobianPoint<E>> for AffinePoint<E> {
+    fn from(p: JacobianPoint<E>) -> Self {
+        p.to_affine()
+    }
+}
+
+/// Two triples represent the same point if they differ by a factor of (Z^2, Z^3).
+impl<E: EllipticCurve> PartialEq for JacobianPoint<E> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.is_inf(), other.is_inf()) {
+            (true, true) => true,
+            (false, false) => {
+                let z1z1 = square(&self.z);
+                let z2z2 = square(&other.z);
+                self.x.clone() * z2z2.clone() == other.x.clone() * z1z1.clone()
+                    && self.y.clone() * z2z2 * other.z.clone()
+                        == other.y.clone() * z1z1 * self.z.clone()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<E: EllipticCurve> Add for JacobianPoint<E> {
+    type Output = JacobianPoint<E>;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.add_jacobian(&other)
+    }
+}
+
+impl<E: EllipticCurve> Add<AffinePoint<E>> for JacobianPoint<E> {
+    type Output = JacobianPoint<E>;
+
+    fn add(self, other: AffinePoint<E>) -> Self::Output {
+        self.add_mixed(&other)
+    }
+}
+
+impl<E: EllipticCurve> Neg for JacobianPoint<E> {
+    type Output = JacobianPoint<E>;
+
+    fn neg(self) -> Self::Output {
+        Self::new(self.x, -self.y, self.z)
+    }
+}
+
+/// Multiplication by a scalar
+impl<E: EllipticCurve> Mul<<E::ScalarField as FiniteField>::T> for JacobianPoint<E> {
+    type Output = JacobianPoint<E>;
+
+    fn mul(self, scalar: <E::ScalarField as FiniteField>::T) -> Self::Output {
+        let k = E::ScalarField::to_bigint(&scalar);
+        let magnitude = k.magnitude();
+
+        // Double-and-Add algorithm
+        let mut point = Self::infinity();
+        for i in (0..magnitude.bits()).rev() {
+            point = point.double();
+
+            if magnitude.bit(i) {
+                point = point + self.clone();
+            }
+        }
+
+        if k.is_negative() {
+            -point
+        } else {
+            point
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{MoonMath, TinyJJ},
+        fields::Fe13_4,
+        polynomial::Polynomial,
+    };
+
+    use super::*;
+
+    fn tinyjj_point() -> AffinePoint<TinyJJ> {
+        AffinePoint::<TinyJJ>::try_new(
+            Polynomial::from(vec![7]).into(),
+            Polynomial::from(vec![11]).into(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_projective_roundtrip() {
+        let p = TinyJJ::generator();
+        let proj = ProjectivePoint::from(p.clone());
+        assert_eq!(proj.to_affine(), p);
+
+        // scaling all coordinates by the same factor gives the same point
+        let c: Fe13_4 = Polynomial::from(vec![3, 1]).into();
+        let scaled = ProjectivePoint::<TinyJJ>::new(
+            proj.x.clone() * c.clone(),
+            proj.y.clone() * c.clone(),
+            c,
+        );
+        assert_eq!(scaled, proj);
+        assert_eq!(scaled.to_affine(), p);
+    }
+
+    #[test]
+    fn test_jacobian_roundtrip() {
+        let p = TinyJJ::generator();
+        let jac = JacobianPoint::from(p.clone());
+        assert_eq!(jac.to_affine(), p);
+
+        let c: Fe13_4 = Polynomial::from(vec![3, 1]).into();
+        let scaled = JacobianPoint::<TinyJJ>::new(
+            jac.x.clone() * c.clone() * c.clone(),
+            jac.y.clone() * c.clone() * c.clone() * c.clone(),
+            c,
+        );
+        assert_eq!(scaled, jac);
+        assert_eq!(scaled.to_affine(), p);
+    }
+
+    #[test]
+    fn test_projective_double() {
+        let p = TinyJJ::generator();
+        let proj = ProjectivePoint::from(p.clone());
+        assert_eq!(proj.double().to_affine(), p.double());
+
+        let q = MoonMath::generator();
+        assert_eq!(
+            ProjectivePoint::from(q.clone()).double().to_affine(),
+            q.double()
+        );
+    }
+
+    #[test]
+    fn test_jacobian_double() {
+        let p = TinyJJ::generator();
+        assert_eq!(
+            JacobianPoint::from(p.clone()).double().to_affine(),
+            p.double()
+        );
+
+        // a = 0 specialization
+        let q = MoonMath::generator();
+        assert_eq!(
+            JacobianPoint::from(q.clone()).double().to_affine(),
+            q.double()
+        );
+    }
+
+    #[test]
+    fn test_projective_add() {
+        let p = TinyJJ::generator();
+        let q = tinyjj_point();
+        let proj_p = ProjectivePoint::from(p.clone()).double();
+        let proj_q = ProjectivePoint::from(q.clone());
+
+        let expected = p.double() + q.clone();
+        assert_eq!((proj_p.clone() + proj_q).to_affine(), expected);
+        assert_eq!((proj_p + q).to_affine(), expected);
+    }
+
+    #[test]
+    fn test_jacobian_add() {
+        let p = TinyJJ::generator();
+        let q = tinyjj_point();
+        let jac_p = JacobianPoint::from(p.clone()).double();
+        let jac_q = JacobianPoint::from(q.clone());
+
+        let expected = p.double() + q.clone();
+        assert_eq!((jac_p.clone() + jac_q).to_affine(), expected);
+        assert_eq!((jac_p + q).to_affine(), expected);
+    }
+
+    #[test]
+    fn test_add_special_cases() {
+        let p = TinyJJ::generator();
+        let jac = JacobianPoint::from(p.clone());
+        let proj = ProjectivePoint::from(p.clone());
+
+        assert!((jac.clone() + -jac.clone()).is_inf());
+        assert!((proj.clone() + -proj.clone()).is_inf());
+        assert_eq!((jac.clone() + jac.clone()).to_affine(), p.double());
+        assert_eq!((proj.clone() + p.clone()).to_affine(), p.double());
+        assert_eq!(jac.clone() + JacobianPoint::infinity(), jac);
+        assert_eq!(ProjectivePoint::infinity() + proj.clone(), proj);
+    }
+
+    #[test]
+    fn test_scalar_mul() {
+        let p = TinyJJ::generator();
+        for k in [1, 2, 3, 17, 123, 14400] {
+            let expected = p.clone() * k;
+            assert_eq!((JacobianPoint::from(p.clone()) * k).to_affine(), expected);
+            assert_eq!((ProjectivePoint::from(p.clone()) * k).to_affine(), expected);
+        }
+        assert!((JacobianPoint::from(p.clone()) * TinyJJ::order()).is_inf());
+        assert!((ProjectivePoint::from(p.clone()) * TinyJJ::order()).is_inf());
+    }
+
+    #[test]
+    fn test_batch_normalize() {
+        let p = JacobianPoint::from(TinyJJ::generator());
+        let points = vec![
+            p.clone(),
+            p.double(),
+            JacobianPoint::infinity(),
+            p.clone() * 7,
+        ];
+        let expected: Vec<_> = points.iter().map(|p| p.to_affine()).collect();
+        assert_eq!(JacobianPoint::batch_normalize(&points), expected);
+
+        let p = ProjectivePoint::from(TinyJJ::generator());
+        let points = vec![p.double(), ProjectivePoint::infinity(), p.clone() * 5];
+        let expected: Vec<_> = points.iter().map(|p| p.to_affine()).collect();
+        assert_eq!(ProjectivePoint::batch_normalize(&points), expected);
+    }
+}
@@ -0,0 +1,324 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::{
+    elliptic_curve::{AffinePoint, EllipticCurve},
+    finite_field::{FiniteField, NonExtendedField},
+    projective::JacobianPoint,
+};
+
+// Faster alternatives to the plain double-and-add in `elliptic_curve.rs`.
+// All of them precompute a few small multiples of the point, so that one
+// addition handles several bits of the scalar at once, and keep the
+// accumulator in Jacobian coordinates to avoid inversions.
+
+/// Returns the width-w non-adjacent form of k, least significant digit first.
+/// Every non-zero digit is odd and lies in (-2^(w-1), 2^(w-1)), and any w
+/// consecutive digits contain at most one non-zero digit.
+pub fn wnaf(k: &BigInt, w: usize) -> Vec<i64> {
+    assert!(
+        (2..=16).contains(&w),
+        "window size must be between 2 and 16"
+    );
+    let modulus = BigInt::one() << w;
+    let half = BigInt::one() << (w - 1);
+
+    let mut k = k.clone();
+    let mut digits = vec![];
+    while !k.is_zero() {
+        let digit = if k.is_odd() {
+            // k mods 2^w, the residue of smallest absolute value
+            let mut d = k.mod_floor(&modulus);
+            if d >= half {
+                d -= &modulus;
+            }
+            k -= &d;
+            d.to_i64().unwrap()
+        } else {
+            0
+        };
+        digits.push(digit);
+        k >>= 1;
+    }
+    digits
+}
+
+/// Splits |k| into base-2^w digits, least significant first.
+fn radix_digits(k: &BigInt, w: usize) -> Vec<usize> {
+    let magnitude = k.magnitude();
+    let windows = magnitude.bits().div_ceil(w as u64);
+    (0..windows)
+        .map(|i| {
+            (0..w as u64)
+                .filter(|j| magnitude.bit(i * w as u64 + j))
+                .map(|j| 1 << j)
+                .sum()
+        })
+        .collect()
+}
+
+impl<E: EllipticCurve> AffinePoint<E> {
+    /// Scalar multiplication using the width-w NAF of the scalar.
+    /// Only the odd multiples P, 3P, ..., (2^(w-1) - 1)P are precomputed,
+    /// negative digits are handled by negating them on the fly.
+    pub fn mul_wnaf(&self, scalar: <E::ScalarField as FiniteField>::T, w: usize) -> Self {
+        // checked before the 2^(w-2) odd multiples are built
+        assert!(
+            (2..=16).contains(&w),
+            "window size must be between 2 and 16"
+        );
+        let k = E::ScalarField::to_bigint(&scalar);
+        if k.is_zero() || self.is_inf() {
+            return AffinePoint::Infinity;
+        }
+
+        let base = JacobianPoint::from(self.clone());
+        let two_p = base.double();
+        let mut odd_multiples = vec![base];
+        for i in 1..1 << (w - 2) {
+            let next = odd_multiples[i - 1].clone() + two_p.clone();
+            odd_multiples.push(next);
+        }
+        let odd_multiples = JacobianPoint::batch_normalize(&odd_multiples);
+
+        let mut acc = JacobianPoint::<E>::infinity();
+        for digit in wnaf(&k, w).into_iter().rev() {
+            acc = acc.double();
+
+            let index = (digit.unsigned_abs() / 2) as usize;
+            if digit > 0 {
+                acc = acc.add_mixed(&odd_multiples[index]);
+            } else if digit < 0 {
+                acc = acc.add_mixed(&-odd_multiples[index].clone());
+            }
+        }
+        acc.to_affine()
+    }
+
+    /// Fixed-window (2^w-ary) scalar multiplication: the scalar is processed
+    /// w bits at a time, with all multiples 0, P, ..., (2^w - 1)P precomputed.
+    pub fn mul_window(&self, scalar: <E::ScalarField as FiniteField>::T, w: usize) -> Self {
+        assert!(
+            (1..=16).contains(&w),
+            "window size must be between 1 and 16"
+        );
+        let k = E::ScalarField::to_bigint(&scalar);
+        if k.is_zero() || self.is_inf() {
+            return AffinePoint::Infinity;
+        }
+
+        let base = JacobianPoint::from(self.clone());
+        let mut multiples = vec![JacobianPoint::infinity(), base.clone()];
+        for i in 2..1 << w {
+            let next = multiples[i - 1].clone() + base.clone();
+            multiples.push(next);
+        }
+        let multiples = JacobianPoint::batch_normalize(&multiples);
+
+        let mut acc = JacobianPoint::<E>::infinity();
+        for digit in radix_digits(&k, w).into_iter().rev() {
+            for _ in 0..w {
+                acc = acc.double();
+            }
+            acc = acc.add_mixed(&multiples[digit]);
+        }
+
+        let result = acc.to_affine();
+        if k.is_negative() {
+            -result
+        } else {
+            result
+        }
+    }
+}
+
+/// Precomputed multiples j * 2^(w*i) * B of a fixed base point B, so that
+/// multiplying B by a scalar takes one addition per w-bit window of the scalar
+/// and no doublings at all.
+#[derive(Clone)]
+pub struct FixedBaseTable<E: EllipticCurve> {
+    window: usize,
+    order: BigInt,
+    table: Vec<Vec<AffinePoint<E>>>,
+}
+
+impl<E: EllipticCurve> FixedBaseTable<E> {
+    /// Builds the table for `base`, whose order must divide `order`.
+    /// Scalars are reduced modulo `order`, so any integer can be used.
+    pub fn new(base: &AffinePoint<E>, order: &BigInt, w: usize) -> Self {
+        assert!(
+            (1..=16).contains(&w),
+            "window size must be between 1 and 16"
+        );
+        let windows = order.bits().div_ceil(w as u64) as usize;
+
+        let mut table = Vec::with_capacity(windows);
+        let mut window_base = JacobianPoint::from(base.clone());
+        for _ in 0..windows {
+            let mut row = vec![JacobianPoint::infinity(), window_base.clone()];
+            for j in 2..1 << w {
+                let next = row[j - 1].clone() + window_base.clone();
+                row.push(next);
+            }
+            // 2^w * window_base is the base of the next window
+            window_base = row[(1 << w) - 1].clone() + window_base;
+            table.push(JacobianPoint::batch_normalize(&row));
+        }
+
+        Self {
+            window: w,
+            order: order.clone(),
+            table,
+        }
+    }
+
+    /// The table for the generator of the curve.
+    pub fn generator(w: usize) -> Self {
+        let order = E::ScalarField::to_bigint(&E::order());
+        Self::new(&E::generator(), &order, w)
+    }
+
+    pub fn mul(&self, scalar: <E::ScalarField as FiniteField>::T) -> AffinePoint<E> {
+        let k = E::ScalarField::to_bigint(&scalar).mod_floor(&self.order);
+
+        let mut acc = JacobianPoint::<E>::infinity();
+        for (row, digit) in self.table.iter().zip(radix_digits(&k, self.window)) {
+            acc = acc.add_mixed(&row[digit]);
+        }
+        acc.to_affine()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{MoonMath, TinyJJ},
+        polynomial::Polynomial,
+    };
+
+    use super::*;
+
+    fn tinyjj_scalars() -> Vec<i16> {
+        let r = TinyJJ::r();
+        let order = TinyJJ::order();
+        let mut scalars: Vec<i16> = (0..18).chain([123, 255, 1000, -1, -2, -123]).collect();
+        scalars.extend([
+            r,
+            r + 1,
+            3 * r + 2,
+            order,
+            order - 1,
+            order + 1000,
+            -order - 3,
+        ]);
+        scalars
+    }
+
+    #[test]
+    fn test_wnaf_digits() {
+        for w in 2..6 {
+            for k in [0i64, 1, 7, 100, 255, 1 << 20, -37, 123456789] {
+                let digits = wnaf(&BigInt::from(k), w);
+
+                let value: BigInt = digits
+                    .iter()
+                    .enumerate()
+                    .map(|(i, d)| BigInt::from(*d) << i)
+                    .sum();
+                assert_eq!(value, BigInt::from(k));
+
+                for (i, d) in digits.iter().enumerate() {
+                    if *d != 0 {
+                        assert!(d % 2 != 0 && d.abs() < 1 << (w - 1));
+                        assert!(digits[i + 1..].iter().take(w - 1).all(|d| *d == 0));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_double_and_add_zero_and_negative() {
+        let p = TinyJJ::generator();
+        let zero = TinyJJ::order() - TinyJJ::order();
+        assert!((p.clone() * zero).is_inf());
+        assert_eq!(p.clone() * -1, -p.clone());
+        assert_eq!(p.clone() * -7, -(p.clone() * 7));
+        assert_eq!(p.clone() * (TinyJJ::order() + 5), p * 5);
+    }
+
+    #[test]
+    fn test_mul_wnaf() {
+        let points = [
+            TinyJJ::generator(),
+            AffinePoint::<TinyJJ>::try_new(
+                Polynomial::from(vec![8]).into(),
+                Polynomial::from(vec![8]).into(),
+            )
+            .unwrap(),
+        ];
+        for p in points {
+            for k in tinyjj_scalars() {
+                let expected = p.clone() * k;
+                for w in 2..6 {
+                    assert_eq!(p.mul_wnaf(k, w), expected, "k = {}, w = {}", k, w);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be between 2 and 16")]
+    fn test_mul_wnaf_window_too_small() {
+        TinyJJ::generator().mul_wnaf(3, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be between 2 and 16")]
+    fn test_mul_wnaf_window_too_large() {
+        TinyJJ::generator().mul_wnaf(3, 40);
+    }
+
+    #[test]
+    fn test_mul_window() {
+        let p = TinyJJ::generator();
+        for k in tinyjj_scalars() {
+            let expected = p.clone() * k;
+            for w in 1..5 {
+                assert_eq!(p.mul_window(k, w), expected, "k = {}, w = {}", k, w);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fixed_base_table() {
+        let p = TinyJJ::generator();
+        for w in [1, 3, 4] {
+            let table = FixedBaseTable::<TinyJJ>::generator(w);
+            for k in tinyjj_scalars() {
+                assert_eq!(table.mul(k), p.clone() * k, "k = {}, w = {}", k, w);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scalar_mul_moonmath() {
+        let p = MoonMath::generator();
+        let table = FixedBaseTable::<MoonMath>::generator(4);
+        for k in [0, 1, 2, 13, 39, -5, 987654321, MoonMath::order() - 1] {
+            let expected = p.clone() * k;
+            assert_eq!(p.mul_wnaf(k, 4), expected);
+            assert_eq!(p.mul_window(k, 3), expected);
+            assert_eq!(table.mul(k), expected);
+        }
+    }
+
+    #[test]
+    fn test_infinity() {
+        let inf = AffinePoint::<TinyJJ>::Infinity;
+        assert!(inf.mul_wnaf(17, 3).is_inf());
+        assert!(inf.mul_window(17, 3).is_inf());
+        assert!((inf * 17).is_inf());
+    }
+}
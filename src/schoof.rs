@@ -0,0 +1,453 @@
+use num_bigint::BigInt;
+use num_integer::{Integer, Roots};
+use num_traits::{One, Signed, Zero};
+
+use crate::{
+    field_element::FieldElement,
+    finite_field::{FiniteField, NonExtendedField},
+    number_theory::{crt, is_prime},
+    polynomial::Polynomial,
+};
+
+// Schoof's algorithm computes the trace of Frobenius t of y^2 = x^3 + ax + b
+// over F_p modulo many small primes l, and recovers t with the CRT once the
+// product of the primes exceeds the width 4√p of the Hasse interval.
+//
+// For an odd prime l, the Frobenius endomorphism φ(x, y) = (x^p, y^p) satisfies
+// φ^2 - tφ + p = 0 on the l-torsion, so t mod l is the unique τ with
+// φ^2(P) + [p mod l]P = [τ]φ(P) for a generic l-torsion point P. We compute in
+// F_p[x, y] / (ψ_l(x), y^2 - x^3 - ax - b), where the division polynomial ψ_l
+// vanishes exactly at the x-coordinates of the l-torsion points.
+//
+// That ring is not a field: an inversion can hit a zero divisor, which reveals
+// a factor of ψ_l. Its roots form a subgroup stable under Frobenius, so the
+// computation simply restarts modulo that factor.
+
+type Poly<M> = Polynomial<FieldElement<M>>;
+
+fn constant<M: FiniteField>(c: i64) -> Poly<M> {
+    Polynomial::new(vec![FieldElement::new(M::from_coefficients(&[
+        BigInt::from(c),
+    ]))])
+}
+
+fn monomial_x<M: FiniteField>() -> Poly<M> {
+    Polynomial::new(vec![FieldElement::zero(), FieldElement::one()])
+}
+
+/// The right-hand side x^3 + ax + b of the curve equation.
+fn curve_polynomial<M: FiniteField>(a: &FieldElement<M>, b: &FieldElement<M>) -> Poly<M> {
+    Polynomial::new(vec![
+        b.clone(),
+        a.clone(),
+        FieldElement::zero(),
+        FieldElement::one(),
+    ])
+}
+
+/// The division polynomials ψ_0, ..., ψ_n of the curve, with the factor y
+/// of the even ones dropped so that all of them are polynomials in x.
+pub fn division_polynomials<M: FiniteField>(
+    a: &FieldElement<M>,
+    b: &FieldElement<M>,
+    n: usize,
+) -> Vec<Poly<M>> {
+    let c = |v: FieldElement<M>| Polynomial::new(vec![v]);
+    let int = |v: i64| FieldElement::<M>::new(M::from_coefficients(&[BigInt::from(v)]));
+    let (a, b) = (a.clone(), b.clone());
+    let (a2, a3, b2) = (
+        a.clone() * a.clone(),
+        a.clone() * a.clone() * a.clone(),
+        b.clone() * b.clone(),
+    );
+    let f = curve_polynomial(&a, &b);
+    let f2 = f.clone() * f.clone();
+
+    let mut psi = vec![
+        constant(0),
+        constant(1),
+        constant(2),
+        // 3x^4 + 6ax^2 + 12bx - a^2
+        Polynomial::new(vec![
+            -a2.clone(),
+            int(12) * b.clone(),
+            int(6) * a.clone(),
+            FieldElement::zero(),
+            int(3),
+        ]),
+        // 4(x^6 + 5ax^4 + 20bx^3 - 5a^2x^2 - 4abx - 8b^2 - a^3)
+        Polynomial::new(vec![
+            int(-32) * b2 - int(4) * a3,
+            int(-16) * a.clone() * b.clone(),
+            int(-20) * a2,
+            int(80) * b,
+            int(20) * a,
+            FieldElement::zero(),
+            int(4),
+        ]),
+    ];
+
+    let two_inv = c(int(2).inverse());
+    for k in 5..=n {
+        let m = k / 2;
+        let next = if k % 2 == 1 {
+            // ψ_{2m+1} = ψ_{m+2} ψ_m^3 - ψ_{m-1} ψ_{m+1}^3, where the even
+            // factors contribute y^4 = f^2
+            let lhs = psi[m + 2].clone() * cube(&psi[m]);
+            let rhs = psi[m - 1].clone() * cube(&psi[m + 1]);
+            if m % 2 == 0 {
+                f2.clone() * lhs - rhs
+            } else {
+                lhs - f2.clone() * rhs
+            }
+        } else {
+            // ψ_{2m} = ψ_m (ψ_{m+2} ψ_{m-1}^2 - ψ_{m-2} ψ_{m+1}^2) / 2y
+            let lhs = psi[m + 2].clone() * psi[m - 1].clone() * psi[m - 1].clone();
+            let rhs = psi[m - 2].clone() * psi[m + 1].clone() * psi[m + 1].clone();
+            psi[m].clone() * (lhs - rhs) * two_inv.clone()
+        };
+        psi.push(next);
+    }
+    psi.truncate(n + 1);
+    psi
+}
+
+fn cube<M: FiniteField>(p: &Poly<M>) -> Poly<M> {
+    p.clone() * p.clone() * p.clone()
+}
+
+/// Arithmetic in F_p[x, y] / (h(x), y^2 - f(x)). Points are stored as (X, Y)
+/// standing for (X(x), y Y(x)), None being the point at infinity.
+struct Ring<M: FiniteField> {
+    h: Poly<M>,
+    f: Poly<M>,
+    a: FieldElement<M>,
+}
+
+type RingPoint<M> = Option<(Poly<M>, Poly<M>)>;
+
+/// A non-trivial factor of the modulus, found by a failed inversion.
+struct ZeroDivisor<M: FiniteField>(Poly<M>);
+
+impl<M: FiniteField> Ring<M> {
+    fn reduce(&self, p: Poly<M>) -> Poly<M> {
+        p % self.h.clone()
+    }
+
+    fn mul(&self, p: &Poly<M>, q: &Poly<M>) -> Poly<M> {
+        self.reduce(p.clone() * q.clone())
+    }
+
+    fn pow(&self, p: &Poly<M>, exp: &BigInt) -> Poly<M> {
+        let mut acc = constant(1);
+        for i in (0..exp.bits()).rev() {
+            acc = self.mul(&acc, &acc);
+            if exp.bit(i) {
+                acc = self.mul(&acc, p);
+            }
+        }
+        acc
+    }
+
+    /// The inverse of p modulo h, or the factor gcd(p, h) of h
+    /// when p is a zero divisor.
+    fn inverse(&self, p: &Poly<M>) -> Result<Poly<M>, ZeroDivisor<M>> {
+        let (mut r0, mut r1) = (self.h.clone(), self.reduce(p.clone()));
+        let (mut s0, mut s1) = (constant(0), constant(1));
+        while !r1.is_zero() {
+            let q = r0.clone() / r1.clone();
+            let r2 = r0 - q.clone() * r1.clone();
+            let s2 = s0 - q * s1.clone();
+            (r0, r1) = (r1, r2);
+            (s0, s1) = (s1, s2);
+        }
+
+        let lead = r0.leading_coefficient().inverse();
+        if r0.degree() == 0 {
+            Ok(self.reduce(s0 * lead))
+        } else {
+            Err(ZeroDivisor(r0 * lead))
+        }
+    }
+
+    fn add(&self, p: &RingPoint<M>, q: &RingPoint<M>) -> Result<RingPoint<M>, ZeroDivisor<M>> {
+        let (x1, y1) = match p {
+            None => return Ok(q.clone()),
+            Some(p) => p.clone(),
+        };
+        let (x2, y2) = match q {
+            None => return Ok(p.clone()),
+            Some(q) => q.clone(),
+        };
+
+        // the slope is y L(x)
+        let l = if x1 == x2 {
+            if y1 != y2 || y1.is_zero() {
+                return Ok(None);
+            }
+            // (3x^2 + a) / 2yY = y (3x^2 + a) / 2Y f
+            let numerator =
+                constant(3) * self.mul(&x1, &x1) + Polynomial::new(vec![self.a.clone()]);
+            let denominator = self.mul(&(constant(2) * y1.clone()), &self.f);
+            self.mul(&numerator, &self.inverse(&denominator)?)
+        } else {
+            let inv = self.inverse(&(x2.clone() - x1.clone()))?;
+            self.mul(&(y2 - y1.clone()), &inv)
+        };
+
+        // x3 = y^2 L^2 - x1 - x2 = f L^2 - x1 - x2
+        let x3 = self.reduce(self.mul(&self.f, &self.mul(&l, &l)) - x1.clone() - x2);
+        let y3 = self.reduce(self.mul(&l, &(x1 - x3.clone())) - y1);
+        Ok(Some((x3, y3)))
+    }
+
+    fn scalar_mul(&self, p: &RingPoint<M>, k: u64) -> Result<RingPoint<M>, ZeroDivisor<M>> {
+        let mut acc = None;
+        for i in (0..u64::BITS - k.leading_zeros()).rev() {
+            acc = self.add(&acc, &acc)?;
+            if k >> i & 1 == 1 {
+                acc = self.add(&acc, p)?;
+            }
+        }
+        Ok(acc)
+    }
+
+    /// Computes t mod l, with h dividing ψ_l.
+    fn trace_mod(&self, l: u64, p: &BigInt) -> Result<u64, ZeroDivisor<M>> {
+        let x = self.reduce(monomial_x());
+        let half = |e: BigInt| (e - 1u32) >> 1;
+
+        // φ(P) = (x^p, y f^((p-1)/2)) and φ^2(P) = (x^(p^2), y f^((p^2-1)/2))
+        let p2 = p * p;
+        let frobenius = Some((self.pow(&x, p), self.pow(&self.f, &half(p.clone()))));
+        let frobenius2 = Some((self.pow(&x, &p2), self.pow(&self.f, &half(p2))));
+
+        let p_mod_l = (p % l).try_into().unwrap();
+        let point = Some((x, constant(1)));
+        let lhs = self.add(&frobenius2, &self.scalar_mul(&point, p_mod_l)?)?;
+        if lhs.is_none() {
+            return Ok(0);
+        }
+
+        let mut multiple = frobenius.clone();
+        for tau in 1..l {
+            if multiple == lhs {
+                return Ok(tau);
+            }
+            multiple = self.add(&multiple, &frobenius)?;
+        }
+        unreachable!("no τ satisfies the characteristic equation of Frobenius")
+    }
+}
+
+/// Whether f has a root in the subfield F_q of M, i.e. gcd(x^q - x, f) ≠ 1.
+pub(crate) fn has_root<M: FiniteField>(f: &Poly<M>, q: &BigInt) -> bool {
+    let ring = Ring {
+        h: f.clone(),
+        f: f.clone(),
+        a: FieldElement::zero(),
+    };
+    let x = monomial_x();
+    let xq = ring.pow(&x, q) - x;
+    ring.inverse(&xq).is_err()
+}
+
+/// Computes t mod 2: t is even exactly when the curve has a point of order 2,
+/// that is when x^3 + ax + b has a root in F_p.
+fn trace_mod_2<M: FiniteField>(f: &Poly<M>, p: &BigInt) -> u64 {
+    if has_root(f, p) {
+        0
+    } else {
+        1
+    }
+}
+
+/// Computes the trace of Frobenius t of y^2 = x^3 + ax + b over the prime
+/// field M with Schoof's algorithm, so that #E(F_p) = p + 1 - t.
+pub fn schoof<M: NonExtendedField>(a: &FieldElement<M>, b: &FieldElement<M>) -> BigInt {
+    schoof_prime_subfield(a, b)
+}
+
+/// Schoof's algorithm for a curve whose coefficients lie in the prime
+/// subfield F_p of M, computing the trace of Frobenius over F_p.
+pub(crate) fn schoof_prime_subfield<M: FiniteField>(
+    a: &FieldElement<M>,
+    b: &FieldElement<M>,
+) -> BigInt {
+    assert!(
+        a.is_in_prime_subfield() && b.is_in_prime_subfield(),
+        "the coefficients must lie in the prime subfield"
+    );
+    let p = M::characteristic();
+    assert!(
+        p > BigInt::from(3),
+        "the characteristic must be greater than 3"
+    );
+    let f = curve_polynomial(a, b);
+
+    // |t| <= 2√p, so t is determined by its residue modulo anything above 4√p
+    let bound = (&p * 16u32).sqrt() + 1u32;
+
+    let mut congruences = vec![(BigInt::from(trace_mod_2(&f, &p)), BigInt::from(2))];
+    let mut product = BigInt::from(2);
+    let mut l = 2u64;
+    while product <= bound {
+        l += 1;
+        if !is_prime(&l) || BigInt::from(l) == p {
+            continue;
+        }
+
+        let psi = division_polynomials(a, b, l as usize).pop().unwrap();
+        let mut ring = Ring {
+            h: psi,
+            f: f.clone(),
+            a: a.clone(),
+        };
+        let t = loop {
+            match ring.trace_mod(l, &p) {
+                Ok(t) => break t,
+                Err(ZeroDivisor(g)) => {
+                    // continue with the smaller of the two factors
+                    let cofactor = ring.h.clone() / g.clone();
+                    ring.h = if g.degree() <= cofactor.degree() {
+                        g
+                    } else {
+                        cofactor
+                    };
+                }
+            }
+        };
+
+        congruences.push((BigInt::from(t), BigInt::from(l)));
+        product *= l;
+    }
+
+    let (t, m) = crt(&congruences).unwrap();
+    // the representative of t in (-m/2, m/2]
+    if &t * 2u32 > m {
+        t - m
+    } else {
+        t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{Bn254, TinyJJ},
+        elliptic_curve::{AffinePoint, EllipticCurve},
+        fields::{Fe13, Fe13_2, Fe43, FeBn254, FfBn254},
+        point_counting::{count_points_bsgs, count_points_naive, frobenius_trace},
+    };
+
+    use super::*;
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    struct Ff1000003;
+
+    impl FiniteField for Ff1000003 {
+        type T = i64;
+
+        fn modulus() -> Self::T {
+            1000003
+        }
+
+        fn one() -> Self::T {
+            1
+        }
+
+        fn zero() -> Self::T {
+            0
+        }
+    }
+
+    impl NonExtendedField for Ff1000003 {
+        fn to_bits(s: i64) -> Vec<bool> {
+            (0..i64::BITS - s.leading_zeros())
+                .rev()
+                .map(|i| s >> i & 1 != 0)
+                .collect()
+        }
+
+        fn to_uint(s: Self::T) -> Option<usize> {
+            s.try_into().ok()
+        }
+
+        fn from_uint(s: usize) -> Option<Self::T> {
+            s.try_into().ok()
+        }
+    }
+
+    type Fe1000003 = FieldElement<Ff1000003>;
+
+    #[test]
+    fn test_division_polynomials() {
+        // y^2 = x^3 + 8x + 8 over F_13, whose points also lie on TinyJJ
+        let (a, b) = (Fe13::new(8), Fe13::new(8));
+        let psi = division_polynomials(&a, &b, 6);
+        assert_eq!(psi[5].degree(), 12);
+        assert_eq!(psi[6].degree(), 16);
+
+        for x in Fe13::elements() {
+            let y2 = x.clone() * x.clone() * x.clone() + a.clone() * x.clone() + b.clone();
+            let Some(y) = y2.sqrt() else { continue };
+            let point = AffinePoint::<TinyJJ>::try_new(
+                Polynomial::new(vec![x.clone()]).into(),
+                Polynomial::new(vec![y]).into(),
+            )
+            .unwrap();
+            let order = (1..).find(|n| (point.clone() * *n).is_inf()).unwrap();
+
+            for (n, psi_n) in psi.iter().enumerate().skip(1) {
+                let value = psi_n
+                    .coefficients()
+                    .iter()
+                    .rev()
+                    .fold(Fe13::zero(), |acc, c| acc * x.clone() + c.clone());
+                // ψ_n vanishes at the n-torsion points other than the 2-torsion
+                let expected = order > 2 && n as i16 % order == 0;
+                assert_eq!(value.is_zero(), expected, "x = {}, n = {}", x, n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_schoof_small_fields() {
+        assert_eq!(schoof(&Fe13::new(8), &Fe13::new(8)), BigInt::from(-6));
+        assert_eq!(schoof(&Fe43::new(0), &Fe43::new(6)), BigInt::from(5));
+
+        for (a, b) in [(1, 1), (2, 5), (0, 3), (7, 0), (11, 12)] {
+            let (a, b) = (Fe43::new(a), Fe43::new(b));
+            let expected = frobenius_trace(&BigInt::from(43), &count_points_naive(&a, &b));
+            assert_eq!(schoof(&a, &b), expected);
+        }
+    }
+
+    #[test]
+    fn test_schoof_large_field() {
+        let p = BigInt::from(1000003);
+        for (a, b) in [(3, 7), (0, 5)] {
+            let (a, b) = (Fe1000003::new(a), Fe1000003::new(b));
+            let expected = frobenius_trace(&p, &count_points_bsgs(&a, &b));
+            assert_eq!(schoof(&a, &b), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the coefficients must lie in the prime subfield")]
+    fn test_schoof_outside_prime_subfield() {
+        let a: Fe13_2 = Polynomial::from(vec![1, 2]).into();
+        schoof_prime_subfield(&a, &Fe13_2::one());
+    }
+
+    #[test]
+    #[ignore = "too slow to run routinely, even in release builds"]
+    fn test_schoof_bn254() {
+        // E(F_p) has prime order r
+        let t = schoof(
+            &FeBn254::new(BigInt::from(0)),
+            &FeBn254::new(BigInt::from(3)),
+        );
+        assert_eq!(FfBn254::modulus() + 1 - t, Bn254::r());
+    }
+}
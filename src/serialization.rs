@@ -0,0 +1,515 @@
+use std::fmt::{self, Display};
+
+use num_bigint::{BigInt, Sign};
+
+use crate::{
+    elliptic_curve::{AffinePoint, CurveError, EllipticCurve},
+    field_element::FieldElement,
+    finite_field::FiniteField,
+};
+
+// Canonical byte encodings.
+//
+// A field element is encoded as its coefficients over the prime subfield,
+// highest degree first, each one as a big-endian integer of fixed length.
+// Points have two encodings:
+//
+// - the Zcash BLS12-381 style, where the three top bits of the first byte are
+//   flags: compression, point at infinity, and whether y is the
+//   lexicographically largest of the two square roots. When the field
+//   encoding has fewer than three unused top bits, a separate leading byte
+//   holds the flags instead.
+// - SEC1, with a leading tag byte: 0x00 for the point at infinity, 0x02 and
+//   0x03 for compressed points with an even or odd y, 0x04 for uncompressed
+//   points. For extension fields, the parity is the sign of y as defined by
+//   `FieldElement::sgn0`.
+//
+// Decoding always checks that the point lies on the curve and, unless an
+// unchecked variant is used, that it belongs to the subgroup of order r.
+
+const COMPRESSION_FLAG: u8 = 0x80;
+const INFINITY_FLAG: u8 = 0x40;
+const SORT_FLAG: u8 = 0x20;
+const FLAGS_MASK: u8 = COMPRESSION_FLAG | INFINITY_FLAG | SORT_FLAG;
+
+const SEC1_INFINITY: u8 = 0x00;
+const SEC1_EVEN: u8 = 0x02;
+const SEC1_ODD: u8 = 0x03;
+const SEC1_UNCOMPRESSED: u8 = 0x04;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodingError {
+    /// The input does not have the length of the encoding.
+    InvalidLength,
+    /// The flags or the tag byte are not a valid combination.
+    InvalidFlags,
+    /// A coefficient is not reduced modulo the characteristic.
+    NonCanonical,
+    /// The coordinates do not satisfy the curve equation,
+    /// or there is no y for the given x.
+    NotOnCurve,
+    /// The point is on the curve, but not in the subgroup of order r.
+    NotInSubgroup,
+}
+
+impl Display for DecodingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodingError::InvalidLength => write!(f, "invalid encoding length"),
+            DecodingError::InvalidFlags => write!(f, "invalid encoding flags"),
+            DecodingError::NonCanonical => write!(f, "non-canonical field element"),
+            DecodingError::NotOnCurve => write!(f, "point is not on the curve"),
+            DecodingError::NotInSubgroup => write!(f, "point is not in the subgroup"),
+        }
+    }
+}
+
+impl std::error::Error for DecodingError {}
+
+impl From<CurveError> for DecodingError {
+    fn from(err: CurveError) -> Self {
+        match err {
+            CurveError::NotOnCurve => DecodingError::NotOnCurve,
+            CurveError::NotInSubgroup => DecodingError::NotInSubgroup,
+        }
+    }
+}
+
+impl<M: FiniteField> FieldElement<M> {
+    /// The number of bytes of one coefficient over the prime subfield.
+    pub fn coefficient_size() -> usize {
+        M::characteristic().bits().div_ceil(8) as usize
+    }
+
+    /// The number of bytes of an encoded element.
+    pub fn encoded_size() -> usize {
+        Self::coefficient_size() * M::degree()
+    }
+
+    /// Big-endian encoding, highest degree coefficient first.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let size = Self::coefficient_size();
+        M::to_coefficients(self.value())
+            .iter()
+            .rev()
+            .flat_map(|c| {
+                let (_, bytes) = c.to_bytes_be();
+                let mut padded = vec![0; size - bytes.len()];
+                padded.extend(bytes);
+                padded
+            })
+            .collect()
+    }
+
+    /// Decodes an element, rejecting coefficients that are not reduced.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodingError> {
+        if bytes.len() != Self::encoded_size() {
+            return Err(DecodingError::InvalidLength);
+        }
+        let p = M::characteristic();
+        let mut coefficients = bytes
+            .chunks(Self::coefficient_size())
+            .map(|chunk| {
+                let c = BigInt::from_bytes_be(Sign::Plus, chunk);
+                if c < p {
+                    Ok(c)
+                } else {
+                    Err(DecodingError::NonCanonical)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        coefficients.reverse();
+        Ok(Self::new(M::from_coefficients(&coefficients)))
+    }
+
+    /// Whether the element is larger than its negation, comparing
+    /// coefficients from the highest degree down.
+    pub fn is_lexicographically_largest(&self) -> bool {
+        let mut lhs = M::to_coefficients(self.value());
+        let mut rhs = M::to_coefficients((-self.clone()).value());
+        lhs.reverse();
+        rhs.reverse();
+        lhs > rhs
+    }
+}
+
+/// Whether the top bits of a field encoding are too few to hold the flags.
+fn needs_flag_byte<M: FiniteField>() -> bool {
+    let spare = 8 * FieldElement::<M>::coefficient_size() as u64 - M::characteristic().bits();
+    spare < 3
+}
+
+/// Sets the flags on a field encoding, prepending a byte if needed.
+fn with_flags<M: FiniteField>(mut bytes: Vec<u8>, flags: u8) -> Vec<u8> {
+    if needs_flag_byte::<M>() {
+        bytes.insert(0, flags);
+    } else {
+        bytes[0] |= flags;
+    }
+    bytes
+}
+
+/// Splits the flags from an encoding, returning them with the remaining bytes.
+fn split_flags<M: FiniteField>(bytes: &[u8]) -> Result<(u8, Vec<u8>), DecodingError> {
+    let (first, rest) = bytes.split_first().ok_or(DecodingError::InvalidLength)?;
+    let flags = first & FLAGS_MASK;
+    if needs_flag_byte::<M>() {
+        if first & !FLAGS_MASK != 0 {
+            return Err(DecodingError::InvalidFlags);
+        }
+        Ok((flags, rest.to_vec()))
+    } else {
+        let mut data = bytes.to_vec();
+        data[0] &= !FLAGS_MASK;
+        Ok((flags, data))
+    }
+}
+
+impl<E: EllipticCurve> AffinePoint<E> {
+    /// The length of a compressed encoding in the Zcash style.
+    pub fn compressed_size() -> usize {
+        FieldElement::<E::BaseField>::encoded_size() + needs_flag_byte::<E::BaseField>() as usize
+    }
+
+    /// The length of an uncompressed encoding in the Zcash style.
+    pub fn uncompressed_size() -> usize {
+        2 * FieldElement::<E::BaseField>::encoded_size()
+            + needs_flag_byte::<E::BaseField>() as usize
+    }
+
+    /// Encodes x and the sign of y.
+    pub fn to_compressed(&self) -> Vec<u8> {
+        let zero = FieldElement::<E::BaseField>::zero().to_bytes();
+        match self {
+            AffinePoint::Infinity => {
+                with_flags::<E::BaseField>(zero, COMPRESSION_FLAG | INFINITY_FLAG)
+            }
+            AffinePoint::XY(x, y) => {
+                let mut flags = COMPRESSION_FLAG;
+                if y.is_lexicographically_largest() {
+                    flags |= SORT_FLAG;
+                }
+                with_flags::<E::BaseField>(x.to_bytes(), flags)
+            }
+        }
+    }
+
+    /// Encodes both coordinates.
+    pub fn to_uncompressed(&self) -> Vec<u8> {
+        match self {
+            AffinePoint::Infinity => {
+                let zero = FieldElement::<E::BaseField>::zero().to_bytes();
+                with_flags::<E::BaseField>([zero.clone(), zero].concat(), INFINITY_FLAG)
+            }
+            AffinePoint::XY(x, y) => {
+                with_flags::<E::BaseField>([x.to_bytes(), y.to_bytes()].concat(), 0)
+            }
+        }
+    }
+
+    /// Decodes a compressed point and checks that it is in the subgroup of order r.
+    pub fn from_compressed(bytes: &[u8]) -> Result<Self, DecodingError> {
+        Self::from_compressed_unchecked(bytes)?.check_subgroup()
+    }
+
+    /// Decodes an uncompressed point and checks that it is in the subgroup of order r.
+    pub fn from_uncompressed(bytes: &[u8]) -> Result<Self, DecodingError> {
+        Self::from_uncompressed_unchecked(bytes)?.check_subgroup()
+    }
+
+    /// Decodes a compressed point, only checking that it lies on the curve.
+    pub fn from_compressed_unchecked(bytes: &[u8]) -> Result<Self, DecodingError> {
+        if bytes.len() != Self::compressed_size() {
+            return Err(DecodingError::InvalidLength);
+        }
+        let (flags, data) = split_flags::<E::BaseField>(bytes)?;
+        if flags & COMPRESSION_FLAG == 0 {
+            return Err(DecodingError::InvalidFlags);
+        }
+
+        if flags & INFINITY_FLAG != 0 {
+            if flags & SORT_FLAG != 0 || data.iter().any(|b| *b != 0) {
+                return Err(DecodingError::InvalidFlags);
+            }
+            return Ok(AffinePoint::Infinity);
+        }
+
+        let x = FieldElement::from_bytes(&data)?;
+        let y = Self::y_from_x(&x).ok_or(DecodingError::NotOnCurve)?;
+        let y = if y.is_lexicographically_largest() == (flags & SORT_FLAG != 0) {
+            y
+        } else {
+            -y
+        };
+        Ok(AffinePoint::new_unchecked(x, y))
+    }
+
+    /// Decodes an uncompressed point, only checking that it lies on the curve.
+    pub fn from_uncompressed_unchecked(bytes: &[u8]) -> Result<Self, DecodingError> {
+        if bytes.len() != Self::uncompressed_size() {
+            return Err(DecodingError::InvalidLength);
+        }
+        let (flags, data) = split_flags::<E::BaseField>(bytes)?;
+        if flags & (COMPRESSION_FLAG | SORT_FLAG) != 0 {
+            return Err(DecodingError::InvalidFlags);
+        }
+
+        if flags & INFINITY_FLAG != 0 {
+            if data.iter().any(|b| *b != 0) {
+                return Err(DecodingError::InvalidFlags);
+            }
+            return Ok(AffinePoint::Infinity);
+        }
+
+        let (x, y) = data.split_at(FieldElement::<E::BaseField>::encoded_size());
+        let x = FieldElement::from_bytes(x)?;
+        let y = FieldElement::from_bytes(y)?;
+        Ok(AffinePoint::try_new(x, y)?)
+    }
+
+    /// SEC1 encoding, compressed or not.
+    pub fn to_sec1(&self, compressed: bool) -> Vec<u8> {
+        match self {
+            AffinePoint::Infinity => vec![SEC1_INFINITY],
+            AffinePoint::XY(x, y) if compressed => {
+                let tag = if y.sgn0() { SEC1_ODD } else { SEC1_EVEN };
+                [vec![tag], x.to_bytes()].concat()
+            }
+            AffinePoint::XY(x, y) => [vec![SEC1_UNCOMPRESSED], x.to_bytes(), y.to_bytes()].concat(),
+        }
+    }
+
+    /// Decodes a SEC1 encoding, compressed or not, and checks that the point
+    /// is in the subgroup of order r.
+    pub fn from_sec1(bytes: &[u8]) -> Result<Self, DecodingError> {
+        let size = FieldElement::<E::BaseField>::encoded_size();
+        let (tag, data) = bytes.split_first().ok_or(DecodingError::InvalidLength)?;
+
+        let point = match *tag {
+            SEC1_INFINITY if data.is_empty() => AffinePoint::Infinity,
+            SEC1_EVEN | SEC1_ODD if data.len() == size => {
+                let x = FieldElement::from_bytes(data)?;
+                let y = Self::y_from_x(&x).ok_or(DecodingError::NotOnCurve)?;
+                let y = if y.sgn0() == (*tag == SEC1_ODD) {
+                    y
+                } else {
+                    -y
+                };
+                AffinePoint::new_unchecked(x, y)
+            }
+            SEC1_UNCOMPRESSED if data.len() == 2 * size => {
+                let (x, y) = data.split_at(size);
+                AffinePoint::try_new(FieldElement::from_bytes(x)?, FieldElement::from_bytes(y)?)?
+            }
+            SEC1_INFINITY | SEC1_EVEN | SEC1_ODD | SEC1_UNCOMPRESSED => {
+                return Err(DecodingError::InvalidLength)
+            }
+            _ => return Err(DecodingError::InvalidFlags),
+        };
+        point.check_subgroup()
+    }
+
+    /// One of the two y coordinates for x, if x is on the curve.
+    fn y_from_x(x: &FieldElement<E::BaseField>) -> Option<FieldElement<E::BaseField>> {
+        let rhs = x.clone() * x.clone() * x.clone() + E::a() * x.clone() + E::b();
+        rhs.sqrt()
+    }
+
+    fn check_subgroup(self) -> Result<Self, DecodingError> {
+        if (self.clone() * E::r()).is_inf() {
+            Ok(self)
+        } else {
+            Err(DecodingError::NotInSubgroup)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{MoonMath, TinyJJ},
+        fields::{Fe13, Fe13_4, Fe43_6, FeBn254},
+        polynomial::Polynomial,
+    };
+
+    use super::*;
+
+    fn tinyjj_g1() -> AffinePoint<TinyJJ> {
+        AffinePoint::try_new_g1(
+            Polynomial::from(vec![8]).into(),
+            Polynomial::from(vec![8]).into(),
+        )
+        .unwrap()
+    }
+
+    fn tinyjj_g2() -> AffinePoint<TinyJJ> {
+        AffinePoint::try_new_g2(
+            Polynomial::from(vec![7, 0, 4]).into(),
+            Polynomial::from(vec![0, 10, 0, 5]).into(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_field_element_bytes() {
+        assert_eq!(Fe13::new(12).to_bytes(), vec![12]);
+
+        let a: Fe13_4 = Polynomial::from(vec![1, 2, 3, 4]).into();
+        assert_eq!(a.to_bytes(), vec![4, 3, 2, 1]);
+        assert_eq!(Fe13_4::from_bytes(&a.to_bytes()), Ok(a));
+
+        let b: Fe43_6 = Polynomial::from(vec![0, 42]).into();
+        assert_eq!(b.to_bytes(), vec![0, 0, 0, 0, 42, 0]);
+
+        let c = FeBn254::new(BigInt::from(258));
+        let bytes = c.to_bytes();
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(bytes[30..], [1, 2]);
+        assert_eq!(FeBn254::from_bytes(&bytes), Ok(c));
+    }
+
+    #[test]
+    fn test_field_element_bytes_invalid() {
+        assert_eq!(Fe13::from_bytes(&[13]), Err(DecodingError::NonCanonical));
+        assert_eq!(Fe13::from_bytes(&[1, 2]), Err(DecodingError::InvalidLength));
+        assert_eq!(
+            Fe13_4::from_bytes(&[0, 0, 200, 0]),
+            Err(DecodingError::NonCanonical)
+        );
+        assert_eq!(
+            FeBn254::from_bytes(&[0xff; 32]),
+            Err(DecodingError::NonCanonical)
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut points = vec![AffinePoint::Infinity];
+        for k in 1..5 {
+            points.push(tinyjj_g1() * k);
+            points.push(tinyjj_g2() * k);
+        }
+        for p in points {
+            assert_eq!(
+                AffinePoint::from_compressed(&p.to_compressed()),
+                Ok(p.clone())
+            );
+            assert_eq!(
+                AffinePoint::from_uncompressed(&p.to_uncompressed()),
+                Ok(p.clone())
+            );
+            assert_eq!(AffinePoint::from_sec1(&p.to_sec1(true)), Ok(p.clone()));
+            assert_eq!(AffinePoint::from_sec1(&p.to_sec1(false)), Ok(p.clone()));
+        }
+
+        let g = AffinePoint::<MoonMath>::try_new_g1(
+            Polynomial::from(vec![27]).into(),
+            Polynomial::from(vec![34]).into(),
+        )
+        .unwrap();
+        for k in 1..13 {
+            let p = g.clone() * k;
+            assert_eq!(
+                AffinePoint::from_compressed(&p.to_compressed()),
+                Ok(p.clone())
+            );
+            assert_eq!(AffinePoint::from_sec1(&p.to_sec1(true)), Ok(p));
+        }
+    }
+
+    #[test]
+    fn test_flags() {
+        // 13 fits in 4 bits, so the flags share the first byte
+        assert_eq!(AffinePoint::<TinyJJ>::compressed_size(), 4);
+        assert_eq!(
+            AffinePoint::<TinyJJ>::Infinity.to_compressed(),
+            vec![0xc0, 0, 0, 0]
+        );
+        assert_eq!(AffinePoint::<TinyJJ>::Infinity.to_uncompressed()[0], 0x40);
+
+        let p = tinyjj_g1();
+        let compressed = p.to_compressed();
+        assert_eq!(compressed[0] & COMPRESSION_FLAG, COMPRESSION_FLAG);
+        assert_eq!(compressed[3] & 0x0f, 8);
+        // the other root of y^2 flips the sort flag
+        assert_eq!((-p).to_compressed()[0] ^ compressed[0], SORT_FLAG);
+
+        // 43 needs 6 bits, so the flags get their own byte
+        assert_eq!(AffinePoint::<MoonMath>::compressed_size(), 7);
+        assert_eq!(AffinePoint::<MoonMath>::uncompressed_size(), 13);
+    }
+
+    #[test]
+    fn test_sec1() {
+        let p = tinyjj_g1();
+        // y = 8 is even
+        assert_eq!(p.to_sec1(true), vec![0x02, 0, 0, 0, 8]);
+        assert_eq!(p.to_sec1(false), vec![0x04, 0, 0, 0, 8, 0, 0, 0, 8]);
+        assert_eq!((-p).to_sec1(true)[0], 0x03);
+        assert_eq!(AffinePoint::<TinyJJ>::Infinity.to_sec1(true), vec![0x00]);
+    }
+
+    #[test]
+    fn test_decoding_errors() {
+        let p = tinyjj_g1();
+
+        let mut bytes = p.to_compressed();
+        bytes[0] &= !COMPRESSION_FLAG;
+        assert_eq!(
+            AffinePoint::<TinyJJ>::from_compressed(&bytes),
+            Err(DecodingError::InvalidFlags)
+        );
+        assert_eq!(
+            AffinePoint::<TinyJJ>::from_compressed(&bytes[1..]),
+            Err(DecodingError::InvalidLength)
+        );
+        assert_eq!(
+            AffinePoint::<TinyJJ>::from_compressed(&[0xe0, 0, 0, 0]),
+            Err(DecodingError::InvalidFlags)
+        );
+        assert_eq!(
+            AffinePoint::<TinyJJ>::from_compressed(&[0xc0, 0, 0, 1]),
+            Err(DecodingError::InvalidFlags)
+        );
+
+        // (8, 9) is not on the curve
+        let mut bytes = p.to_uncompressed();
+        bytes[7] = 9;
+        assert_eq!(
+            AffinePoint::<TinyJJ>::from_uncompressed(&bytes),
+            Err(DecodingError::NotOnCurve)
+        );
+
+        // the generator of the whole group is not in the subgroup of order r
+        let g = TinyJJ::generator();
+        assert_eq!(
+            AffinePoint::<TinyJJ>::from_compressed(&g.to_compressed()),
+            Err(DecodingError::NotInSubgroup)
+        );
+        assert_eq!(
+            AffinePoint::<TinyJJ>::from_compressed_unchecked(&g.to_compressed()),
+            Ok(g.clone())
+        );
+        assert_eq!(
+            AffinePoint::<TinyJJ>::from_uncompressed_unchecked(&g.to_uncompressed()),
+            Ok(g.clone())
+        );
+        assert_eq!(
+            AffinePoint::<TinyJJ>::from_sec1(&g.to_sec1(true)),
+            Err(DecodingError::NotInSubgroup)
+        );
+
+        assert_eq!(
+            AffinePoint::<TinyJJ>::from_sec1(&[0x05, 0, 0, 0, 8]),
+            Err(DecodingError::InvalidFlags)
+        );
+        assert_eq!(
+            AffinePoint::<TinyJJ>::from_sec1(&[0x02, 0, 0, 8]),
+            Err(DecodingError::InvalidLength)
+        );
+        assert_eq!(
+            AffinePoint::<TinyJJ>::from_sec1(&[]),
+            Err(DecodingError::InvalidLength)
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use num_bigint::BigInt;
+
+use crate::{
+    elliptic_curve::{AffinePoint, EllipticCurve},
+    field_element::FieldElement,
+    finite_field::{FiniteField, NonExtendedField},
+    matrix::Matrix,
+};
+
+// Subfields.
+//
+// A curve over F_{q^k} whose coefficients lie in a subfield F_{q^m} is also
+// a curve over F_{q^m}, and its points with coordinates in F_{q^m} form the
+// subgroup E(F_{q^m}). For a pairing-friendly curve this is where G1 lives:
+// TinyJJ is defined over F_13, yet its G1 points are stored with four
+// coefficients each. The descended curve has the same equation over the
+// subfield, and points move between the two by mapping their coordinates.
+//
+// The subfield is fixed by the image g in F_{q^k} of its generator, so the
+// element c_0 + c_1 t + c_2 t^2 + ... is embedded as c_0 + c_1 g + c_2 g^2
+// + .... Going back is a linear system over the prime field, which has no
+// solution when the value is not in the subfield. For the prime subfield
+// every g works, since its elements are constants.
+
+/// Embeds an element of the subfield `S` of `M` into `M`, sending the
+/// generator of `S` to `generator`.
+pub fn embed<S: FiniteField, M: FiniteField>(
+    value: &FieldElement<S>,
+    generator: &FieldElement<M>,
+) -> FieldElement<M> {
+    S::to_coefficients(value.value())
+        .iter()
+        .rev()
+        .fold(FieldElement::zero(), |acc, c| {
+            let c = M::from_coefficients(std::slice::from_ref(c));
+            acc * generator.clone() + FieldElement::new(c)
+        })
+}
+
+/// The inverse of `embed`: finds the coordinates of the value in the basis
+/// 1, g, g^2, ... of the subfield by solving a linear system over the prime
+/// field `P`. Returns None when the value is not in the subfield.
+pub fn restrict<S: FiniteField, M: FiniteField, P: NonExtendedField>(
+    value: &FieldElement<M>,
+    generator: &FieldElement<M>,
+) -> Option<FieldElement<S>> {
+    let lift = |c: &BigInt| FieldElement::new(P::from_bigint(c).unwrap());
+    let coefficients = |v: &FieldElement<M>| -> Vec<FieldElement<P>> {
+        M::to_coefficients(v.value()).iter().map(lift).collect()
+    };
+
+    let mut power = FieldElement::one();
+    let mut basis = vec![];
+    for _ in 0..S::degree() {
+        basis.push(coefficients(&power));
+        power = power * generator.clone();
+    }
+
+    let solution = Matrix::from_rows(basis)
+        .transpose()
+        .solve(&coefficients(value))?;
+    let solution: Vec<BigInt> = solution.iter().map(|c| P::to_bigint(c.value())).collect();
+    Some(FieldElement::new(S::from_coefficients(&solution)))
+}
+
+/// The base field of the descended curve of `E`.
+pub type DescendedField<E> = <<E as SubfieldCurve>::Descended as EllipticCurve>::BaseField;
+
+pub trait SubfieldCurve: EllipticCurve {
+    /// The curve with the same equation over a subfield of the base field.
+    type Descended: EllipticCurve;
+
+    /// The image in the base field of the generator of the subfield. The
+    /// default is only right for the prime subfield.
+    fn descent_generator() -> FieldElement<Self::BaseField> {
+        FieldElement::one()
+    }
+
+    /// Embeds an element of the subfield into the base field.
+    fn lift_coordinate(
+        value: &FieldElement<DescendedField<Self>>,
+    ) -> FieldElement<Self::BaseField> {
+        embed(value, &Self::descent_generator())
+    }
+
+    /// The inverse of `lift_coordinate`, None outside the subfield.
+    fn descend_coordinate(
+        value: &FieldElement<Self::BaseField>,
+    ) -> Option<FieldElement<DescendedField<Self>>> {
+        restrict::<_, _, Self::ScalarField>(value, &Self::descent_generator())
+    }
+
+    /// Checks that the subfield divides the base field, that the generator
+    /// is sent to a root of the subfield's modulus and that both curves have
+    /// the same coefficients.
+    fn is_valid_descent() -> bool {
+        let degree = DescendedField::<Self>::degree();
+        let t = FieldElement::<DescendedField<Self>>::new(
+            DescendedField::<Self>::from_coefficients(&[BigInt::from(0), BigInt::from(1)]),
+        );
+        let t_d = (1..degree).fold(t.clone(), |acc, _| acc * t.clone());
+        let g_d = (1..degree).fold(Self::lift_coordinate(&t), |acc, _| {
+            acc * Self::lift_coordinate(&t)
+        });
+
+        Self::BaseField::degree() % degree == 0
+            && Self::lift_coordinate(&t_d) == g_d
+            && Self::lift_coordinate(&Self::Descended::a()) == Self::a()
+            && Self::lift_coordinate(&Self::Descended::b()) == Self::b()
+    }
+}
+
+impl<E: SubfieldCurve> AffinePoint<E> {
+    /// Embeds a point of the descended curve into the curve.
+    pub fn lift(p: &AffinePoint<E::Descended>) -> Self {
+        match p {
+            AffinePoint::XY(x, y) => {
+                AffinePoint::new_unchecked(E::lift_coordinate(x), E::lift_coordinate(y))
+            }
+            AffinePoint::Infinity => AffinePoint::Infinity,
+        }
+    }
+
+    /// The point over the subfield, or None when one of the coordinates is
+    /// not in it.
+    pub fn try_descend(&self) -> Option<AffinePoint<E::Descended>> {
+        match self {
+            AffinePoint::XY(x, y) => Some(AffinePoint::new_unchecked(
+                E::descend_coordinate(x)?,
+                E::descend_coordinate(y)?,
+            )),
+            AffinePoint::Infinity => Some(AffinePoint::Infinity),
+        }
+    }
+
+    /// Whether the point is defined over the subfield.
+    pub fn is_subfield_rational(&self) -> bool {
+        self.try_descend().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{Bn254, MoonMath, MoonMathFp, TinyJJ, TinyJJFp, TinyJJTwist},
+        elliptic_curve::points,
+        fields::{Fe13, Fe13_4, Ff13, Ff13_2},
+        pairing::Pairing,
+        polynomial::Polynomial,
+        twist::Twist,
+    };
+
+    use super::*;
+
+    fn is_on_curve<E: EllipticCurve>(p: &AffinePoint<E>) -> bool {
+        match p {
+            AffinePoint::XY(x, y) => AffinePoint::<E>::is_on_curve(x, y),
+            AffinePoint::Infinity => true,
+        }
+    }
+
+    // TinyJJ again, descended to F_13^2 instead of F_13
+    #[derive(Debug, Clone, PartialEq)]
+    struct TinyJJ2;
+
+    impl EllipticCurve for TinyJJ2 {
+        type BaseField = Ff13_2;
+        type ScalarField = Ff13;
+
+        fn a() -> FieldElement<Self::BaseField> {
+            Polynomial::from(vec![8]).into()
+        }
+
+        fn b() -> FieldElement<Self::BaseField> {
+            Polynomial::from(vec![8]).into()
+        }
+
+        fn generator() -> AffinePoint<Self> {
+            AffinePoint::try_new(
+                Polynomial::from(vec![1, 1]).into(),
+                Polynomial::from(vec![10, 5]).into(),
+            )
+            .unwrap()
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct TinyJJ4;
+
+    impl EllipticCurve for TinyJJ4 {
+        type BaseField = <TinyJJ as EllipticCurve>::BaseField;
+        type ScalarField = Ff13;
+
+        fn a() -> FieldElement<Self::BaseField> {
+            TinyJJ::a()
+        }
+
+        fn b() -> FieldElement<Self::BaseField> {
+            TinyJJ::b()
+        }
+
+        fn generator() -> AffinePoint<Self> {
+            AffinePoint::lift(&TinyJJ2::generator())
+        }
+    }
+
+    impl SubfieldCurve for TinyJJ4 {
+        type Descended = TinyJJ2;
+
+        fn descent_generator() -> FieldElement<Self::BaseField> {
+            // t^2 = -2 = x^4
+            Polynomial::from(vec![0, 0, 1]).into()
+        }
+    }
+
+    #[test]
+    fn test_subfield_is_valid_descent() {
+        assert!(TinyJJ::is_valid_descent());
+        assert!(MoonMath::is_valid_descent());
+        assert!(Bn254::is_valid_descent());
+        assert!(TinyJJ4::is_valid_descent());
+    }
+
+    #[test]
+    fn test_subfield_embed_restrict() {
+        let g = TinyJJ::subfield_generator();
+        for value in FieldElement::<Ff13_2>::elements() {
+            let embedded: Fe13_4 = embed(&value, &g);
+            assert_eq!(restrict::<Ff13_2, _, Ff13>(&embedded, &g), Some(value));
+        }
+        // x itself is not in F_13^2
+        let x: Fe13_4 = Polynomial::from(vec![0, 1]).into();
+        assert_eq!(restrict::<Ff13_2, _, Ff13>(&x, &g), None);
+    }
+
+    #[test]
+    fn test_subfield_lift_descend() {
+        let p = AffinePoint::<TinyJJ>::try_new(
+            Polynomial::from(vec![8]).into(),
+            Polynomial::from(vec![8]).into(),
+        )
+        .unwrap();
+        let descended = p.try_descend().unwrap();
+        assert_eq!(
+            descended,
+            AffinePoint::<TinyJJFp>::try_new(Fe13::new(8), Fe13::new(8)).unwrap()
+        );
+        assert_eq!(AffinePoint::lift(&descended), p);
+        assert!(p.is_subfield_rational());
+
+        // G2 points have coordinates outside F_13
+        let q = TinyJJ::untwist(&TinyJJTwist::generator());
+        assert!(TinyJJ::is_valid_g2(&q));
+        assert_eq!(q.try_descend(), None);
+        assert!(!q.is_subfield_rational());
+
+        assert_eq!(
+            AffinePoint::<TinyJJ>::Infinity.try_descend(),
+            Some(AffinePoint::Infinity)
+        );
+        assert_eq!(
+            AffinePoint::<TinyJJ>::lift(&AffinePoint::Infinity),
+            AffinePoint::Infinity
+        );
+    }
+
+    #[test]
+    fn test_subfield_points() {
+        // E(F_13) is exactly the set of points that descend
+        let descended: Vec<_> = points::<TinyJJFp>().collect();
+        assert_eq!(descended.len() as i16, TinyJJFp::order());
+        for p in &descended {
+            let lifted = AffinePoint::<TinyJJ>::lift(p);
+            assert!(is_on_curve(&lifted));
+            assert_eq!(lifted.frobenius(1), lifted);
+            assert_eq!(lifted.try_descend().as_ref(), Some(p));
+        }
+
+        let lifted: Vec<_> = points::<TinyJJ2>()
+            .map(|p| AffinePoint::<TinyJJ4>::lift(&p))
+            .collect();
+        for p in &lifted {
+            assert!(is_on_curve(p));
+            assert_eq!(p.frobenius(2), *p);
+        }
+    }
+
+    #[test]
+    fn test_subfield_group_law() {
+        let p = MoonMathFp::generator();
+        let q = p.clone() + p.clone() + p.clone();
+        let lifted = AffinePoint::<MoonMath>::lift(&p) + AffinePoint::<MoonMath>::lift(&q);
+        assert_eq!(lifted, AffinePoint::lift(&(p.clone() + q)));
+        assert_eq!(lifted.try_descend(), Some(p.clone() * 4));
+
+        // the descended G1 generator generates the lifted G1
+        let g1 = AffinePoint::<MoonMath>::lift(&(p * 3));
+        assert!(MoonMath::is_valid_g1(&g1));
+        let p = TinyJJFp::generator() * 4;
+        assert!(TinyJJ::is_valid_g1(&AffinePoint::lift(&p)));
+    }
+}
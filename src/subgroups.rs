@@ -0,0 +1,152 @@
+use num_integer::Integer;
+
+use crate::{
+    elliptic_curve::{AffinePoint, EllipticCurve},
+    field_element::FieldElement,
+    finite_field::{FiniteField, NonExtendedField},
+    pairing::Pairing,
+};
+
+// Finding G1 and G2.
+//
+// Over F_{q^k}, the r-torsion E[r] is a product of two cyclic groups of order
+// r. The trace map Tr(P) = P + π(P) + ... + π^(k-1)(P), π being the Frobenius
+// endomorphism, sends E[r] onto G1 = E(F_q)[r], the points fixed by π. The
+// anti-trace map k * P - Tr(P) sends it onto G2, the points of trace zero.
+//
+// To find generators we sample points of the curve by increasing x, multiply
+// them by the cofactor to land in E[r], and project them. The first sampled
+// point with a non-trivial projection gives the canonical generator.
+
+/// Points of the curve for x = 0, 1, 2, ... in the order of
+/// `FieldElement::elements`, taking the root y with sgn0(y) = 0.
+fn sample_points<E: EllipticCurve>() -> impl Iterator<Item = AffinePoint<E>> {
+    FieldElement::<E::BaseField>::elements().filter_map(|x| {
+        let y2 = x.clone() * x.clone() * x.clone() + E::a() * x.clone() + E::b();
+        let y = y2.sqrt()?;
+        let y = if y.sgn0() { -y } else { y };
+        Some(AffinePoint::new_unchecked(x, y))
+    })
+}
+
+impl<E: EllipticCurve> AffinePoint<E> {
+    /// Maps the point into E[r]: it is first multiplied by the part of the
+    /// order coprime to r, then by r until the next multiple vanishes.
+    pub fn clear_cofactor(&self) -> Self {
+        let mut cofactor = E::ScalarField::to_bigint(&E::order());
+        let r = E::ScalarField::to_bigint(&E::r());
+        while cofactor.is_multiple_of(&r) {
+            cofactor /= &r;
+        }
+
+        let mut point = self.mul_double_and_add(&cofactor);
+        loop {
+            let next = point.mul_double_and_add(&r);
+            if next.is_inf() {
+                return point;
+            }
+            point = next;
+        }
+    }
+}
+
+/// Projects an r-torsion point into G1 with the trace map.
+pub fn project_g1<E: EllipticCurve>(p: &AffinePoint<E>) -> AffinePoint<E> {
+    p.trace_map()
+}
+
+/// Projects an r-torsion point into G2 with the anti-trace map.
+pub fn project_g2<E: EllipticCurve>(p: &AffinePoint<E>) -> AffinePoint<E> {
+    p.anti_trace_map()
+}
+
+/// The canonical generator of G1: the first non-trivial trace of a
+/// sampled point of E[r].
+pub fn g1_generator<E: Pairing>() -> AffinePoint<E> {
+    let generator = sample_points::<E>()
+        .map(|p| project_g1(&p.clear_cofactor()))
+        .find(|p| !p.is_inf())
+        .expect("G1 is trivial");
+    debug_assert!(E::is_valid_g1(&generator));
+    generator
+}
+
+/// The canonical generator of G2: the first non-trivial anti-trace of
+/// a sampled point of E[r].
+pub fn g2_generator<E: Pairing>() -> AffinePoint<E> {
+    let generator = sample_points::<E>()
+        .map(|p| project_g2(&p.clear_cofactor()))
+        .find(|p| !p.is_inf())
+        .expect("G2 is trivial");
+    debug_assert!(E::is_valid_g2(&generator));
+    generator
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{MoonMath, TinyJJ},
+        polynomial::Polynomial,
+    };
+
+    use super::*;
+
+    fn assert_generators<E: Pairing>() {
+        let r = E::r();
+        let g1 = g1_generator::<E>();
+        let g2 = g2_generator::<E>();
+
+        assert!(!g1.is_inf() && !g2.is_inf());
+        assert!(E::is_valid_g1(&g1));
+        assert!(E::is_valid_g2(&g2));
+        assert!(!E::is_valid_g1(&g2));
+        assert!(!E::is_valid_g2(&g1));
+        assert!((g1.clone() * r.clone()).is_inf());
+        assert!((g2.clone() * r).is_inf());
+
+        // the generators are usable for the pairing
+        assert!(AffinePoint::<E>::try_new_g1(g1.x().unwrap(), g1.y().unwrap()).is_ok());
+        assert!(AffinePoint::<E>::try_new_g2(g2.x().unwrap(), g2.y().unwrap()).is_ok());
+        assert_ne!(g1.pairing(&g2), FieldElement::one());
+    }
+
+    #[test]
+    fn test_generators_tinyjj() {
+        assert_generators::<TinyJJ>();
+
+        // the first points of E(F_13)[5]
+        let g1 = g1_generator::<TinyJJ>();
+        assert!(g1.x().unwrap().is_in_prime_subfield());
+        assert!(g1.y().unwrap().is_in_prime_subfield());
+    }
+
+    #[test]
+    fn test_generators_moonmath() {
+        assert_generators::<MoonMath>();
+    }
+
+    #[test]
+    fn test_clear_cofactor() {
+        let p = TinyJJ::generator().clear_cofactor();
+        assert!(!p.is_inf());
+        assert!((p * TinyJJ::r()).is_inf());
+
+        // a point of order 2 has no r-torsion component
+        let p = AffinePoint::<TinyJJ>::try_new(
+            Polynomial::from(vec![4]).into(),
+            Polynomial::from(vec![0]).into(),
+        )
+        .unwrap();
+        assert!(p.clear_cofactor().is_inf());
+    }
+
+    #[test]
+    fn test_projections() {
+        let p = TinyJJ::generator().clear_cofactor();
+        let g1 = project_g1(&p);
+        let g2 = project_g2(&p);
+        // P = (Tr(P) + (k P - Tr(P))) / k
+        let k = TinyJJ::embedding_degree() as i16;
+        assert_eq!(g1 + g2, p * k);
+    }
+}
@@ -0,0 +1,255 @@
+use std::fmt::{self, Display};
+
+use crate::{
+    elliptic_curve::{AffinePoint, EllipticCurve},
+    field_element::FieldElement,
+    finite_field::{FiniteField, NonExtendedField},
+    pairing::Pairing,
+    schoof::division_polynomials,
+    subgroups::{g1_generator, g2_generator},
+};
+
+// The r-torsion E[r] of a curve over F_{q^k} is isomorphic to Z_r x Z_r: it
+// has r^2 points and r + 1 cyclic subgroups of order r. Exactly one of them
+// is G1, the points defined over F_q, and exactly one is G2, the points of
+// trace zero. All the others are mixed and useless for the pairing.
+
+/// Where a point of E[r] lies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TorsionClass {
+    /// The point at infinity, which lies in every subgroup.
+    Identity,
+    /// Fixed by Frobenius, so Tr(P) = k * P.
+    G1,
+    /// In the trace-zero subgroup, Tr(P) = O.
+    G2,
+    /// In one of the other r - 1 subgroups.
+    Other,
+}
+
+impl Display for TorsionClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TorsionClass::Identity => write!(f, "identity"),
+            TorsionClass::G1 => write!(f, "G1"),
+            TorsionClass::G2 => write!(f, "G2 (trace zero)"),
+            TorsionClass::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// Classifies a point of E[r].
+pub fn classify<E: Pairing>(p: &AffinePoint<E>) -> TorsionClass {
+    if p.is_inf() {
+        TorsionClass::Identity
+    } else if E::is_valid_g1(p) {
+        TorsionClass::G1
+    } else if E::is_valid_g2(p) {
+        TorsionClass::G2
+    } else {
+        TorsionClass::Other
+    }
+}
+
+/// Lists E[r] by iterating over all elements x of the base field, keeping
+/// the roots of the division polynomial ψ_r together with both square roots
+/// of x^3 + ax + b. Only practical for tiny fields.
+pub fn r_torsion_by_enumeration<E: EllipticCurve>() -> Vec<AffinePoint<E>> {
+    let r = E::ScalarField::to_uint(E::r()).expect("r is too large to enumerate E[r]");
+    let psi = division_polynomials(&E::a(), &E::b(), r).pop().unwrap();
+
+    let mut points = vec![AffinePoint::Infinity];
+    for x in FieldElement::<E::BaseField>::elements() {
+        let value = psi
+            .coefficients()
+            .iter()
+            .rev()
+            .fold(FieldElement::zero(), |acc, c| acc * x.clone() + c.clone());
+        if !value.is_zero() {
+            continue;
+        }
+        let y2 = x.clone() * x.clone() * x.clone() + E::a() * x.clone() + E::b();
+        if let Some(y) = y2.sqrt() {
+            points.push(AffinePoint::new_unchecked(x.clone(), -y.clone()));
+            points.push(AffinePoint::new_unchecked(x, y));
+        }
+    }
+    points
+}
+
+/// Lists E[r] as all the combinations a * P + b * Q of the generators
+/// of G1 and G2, which works for any curve with a small enough r.
+pub fn r_torsion<E: Pairing>() -> Vec<AffinePoint<E>> {
+    let r = E::ScalarField::to_uint(E::r()).expect("r is too large to enumerate E[r]");
+    let (p, q) = (g1_generator::<E>(), g2_generator::<E>());
+
+    let mut points = Vec::with_capacity(r * r);
+    let mut a_p = AffinePoint::Infinity;
+    for _ in 0..r {
+        let mut point = a_p.clone();
+        for _ in 0..r {
+            points.push(point.clone());
+            point = point + q.clone();
+        }
+        a_p = a_p + p.clone();
+    }
+    points
+}
+
+/// A cyclic subgroup of order r inside E[r].
+pub struct TorsionSubgroup<E: EllipticCurve> {
+    pub generator: AffinePoint<E>,
+    pub class: TorsionClass,
+}
+
+/// The structure of E[r]: its points grouped into the r + 1
+/// cyclic subgroups of order r.
+pub struct TorsionReport<E: EllipticCurve> {
+    pub points: Vec<(AffinePoint<E>, TorsionClass)>,
+    pub subgroups: Vec<TorsionSubgroup<E>>,
+}
+
+impl<E: Pairing> TorsionReport<E> {
+    /// Builds the report from a list of all points of E[r].
+    pub fn new(torsion: Vec<AffinePoint<E>>) -> Self {
+        let points: Vec<_> = torsion
+            .into_iter()
+            .map(|p| {
+                let class = classify(&p);
+                (p, class)
+            })
+            .collect();
+
+        // every non-trivial point generates one of the subgroups,
+        // so take the first point not yet covered each time
+        let mut subgroups: Vec<TorsionSubgroup<E>> = vec![];
+        let mut covered: Vec<AffinePoint<E>> = vec![AffinePoint::Infinity];
+        for (p, class) in &points {
+            if covered.contains(p) {
+                continue;
+            }
+            let mut multiple = p.clone();
+            while !multiple.is_inf() {
+                covered.push(multiple.clone());
+                multiple = multiple + p.clone();
+            }
+            subgroups.push(TorsionSubgroup {
+                generator: p.clone(),
+                class: *class,
+            });
+        }
+
+        Self { points, subgroups }
+    }
+
+    /// The number of points in each class.
+    pub fn count(&self, class: TorsionClass) -> usize {
+        self.points.iter().filter(|(_, c)| *c == class).count()
+    }
+}
+
+impl<E: Pairing> Display for TorsionReport<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "E[{}] over a field of degree {}, embedding degree {}",
+            E::r(),
+            E::BaseField::degree(),
+            E::embedding_degree()
+        )?;
+        writeln!(
+            f,
+            "{} points, {} subgroups",
+            self.points.len(),
+            self.subgroups.len()
+        )?;
+        for class in [
+            TorsionClass::Identity,
+            TorsionClass::G1,
+            TorsionClass::G2,
+            TorsionClass::Other,
+        ] {
+            writeln!(
+                f,
+                "  {:<16} {:>6} points",
+                class.to_string(),
+                self.count(class)
+            )?;
+        }
+
+        writeln!(f, "{:<16} | generator", "subgroup")?;
+        for subgroup in &self.subgroups {
+            let (x, y) = subgroup.generator.xy().unwrap();
+            writeln!(f, "{:<16} | ({}, {})", subgroup.class.to_string(), x, y)?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints the structure of E[r], built from the generators of G1 and G2.
+pub fn print_torsion_report<E: Pairing>() {
+    println!("{}", TorsionReport::new(r_torsion::<E>()));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curves::{MoonMath, Tiny7, TinyJJ};
+
+    use super::*;
+
+    fn assert_structure<E: Pairing>(report: &TorsionReport<E>) {
+        let r = E::ScalarField::to_uint(E::r()).unwrap();
+        assert_eq!(report.points.len(), r * r);
+        assert_eq!(report.subgroups.len(), r + 1);
+        assert_eq!(report.count(TorsionClass::Identity), 1);
+        assert_eq!(report.count(TorsionClass::G1), r - 1);
+        assert_eq!(report.count(TorsionClass::G2), r - 1);
+        assert_eq!(report.count(TorsionClass::Other), (r - 1) * (r - 1));
+
+        let classes: Vec<_> = report.subgroups.iter().map(|s| s.class).collect();
+        assert_eq!(
+            classes.iter().filter(|c| **c == TorsionClass::G1).count(),
+            1
+        );
+        assert_eq!(
+            classes.iter().filter(|c| **c == TorsionClass::G2).count(),
+            1
+        );
+        for (p, _) in &report.points {
+            assert!((p.clone() * E::r()).is_inf());
+        }
+    }
+
+    #[test]
+    fn test_torsion_tinyjj() {
+        let mut enumerated = r_torsion_by_enumeration::<TinyJJ>();
+        let mut generated = r_torsion::<TinyJJ>();
+        enumerated.sort_by_key(|p| p.to_uncompressed());
+        generated.sort_by_key(|p| p.to_uncompressed());
+        assert_eq!(enumerated, generated);
+
+        let report = TorsionReport::new(enumerated);
+        assert_structure(&report);
+        assert!(report.to_string().starts_with(
+            "E[5] over a field of degree 4, embedding degree 4\n25 points, 6 subgroups"
+        ));
+    }
+
+    #[test]
+    fn test_torsion_moonmath() {
+        let report = TorsionReport::new(r_torsion::<MoonMath>());
+        assert_structure(&report);
+    }
+
+    #[test]
+    fn test_torsion_by_enumeration() {
+        assert_eq!((Tiny7::r(), Tiny7::embedding_degree()), (7, 2));
+
+        let mut enumerated = r_torsion_by_enumeration::<Tiny7>();
+        let mut generated = r_torsion::<Tiny7>();
+        enumerated.sort_by_key(|p| p.to_uncompressed());
+        generated.sort_by_key(|p| p.to_uncompressed());
+        assert_eq!(enumerated, generated);
+        assert_structure(&TorsionReport::new(enumerated));
+    }
+}
@@ -0,0 +1,310 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::Zero;
+
+use crate::{
+    elliptic_curve::{AffinePoint, CurveError, EllipticCurve},
+    field_element::FieldElement,
+    finite_field::{FiniteField, NonExtendedField},
+    pairing::Pairing,
+    point_counting, subfield,
+};
+
+// Twists.
+//
+// G2 is made of the points of trace zero of E(F_{q^k}), so its coordinates
+// have k coefficients each. A twist of degree d is a curve E' defined over
+// the smaller field F_{q^(k/d)} together with an isomorphism into E over
+// F_{q^k}. For some ω in F_{q^k} with ω^d in F_{q^(k/d)}, the twist is
+//
+//     E': y^2 = x^3 + a/ω^4 * x + b/ω^6
+//
+// and the untwisting map is ψ(x, y) = (x * ω^2, y * ω^3). Quadratic twists
+// exist for every curve, quartic ones need b = 0 and sextic ones need a = 0.
+// ψ maps the r-torsion of E'(F_{q^(k/d)}) onto G2, so G2 points can be
+// stored and added on the twist, and only untwisted for the Miller loop.
+
+/// The base field of the twist of `E`.
+pub type TwistField<E> = <<E as Twist>::TwistedCurve as EllipticCurve>::BaseField;
+
+pub trait Twist: Pairing {
+    /// The twisted curve, defined over F_{q^(k/d)}.
+    type TwistedCurve: EllipticCurve;
+
+    /// The degree d of the twist: 2, 4 or 6.
+    fn twist_degree() -> usize;
+
+    /// The element ω of F_{q^k} used to build the twist.
+    fn omega() -> FieldElement<Self::BaseField>;
+
+    /// The image in F_{q^k} of the generator of F_{q^(k/d)}, which fixes the
+    /// embedding of the twist's base field into the base field of the curve.
+    fn subfield_generator() -> FieldElement<Self::BaseField>;
+
+    /// Embeds an element of F_{q^(k/d)} into F_{q^k}.
+    fn embed(value: &FieldElement<TwistField<Self>>) -> FieldElement<Self::BaseField> {
+        subfield::embed(value, &Self::subfield_generator())
+    }
+
+    /// The inverse of `embed`: finds the coordinates of the value in the
+    /// basis 1, g, g^2, ... of the subfield by solving a linear system over
+    /// the prime field. Returns None when the value is not in the subfield.
+    fn restrict(value: &FieldElement<Self::BaseField>) -> Option<FieldElement<TwistField<Self>>> {
+        subfield::restrict::<_, _, Self::ScalarField>(value, &Self::subfield_generator())
+    }
+
+    /// The untwisting map ψ(x, y) = (x * ω^2, y * ω^3) from the twist into the curve.
+    fn untwist(q: &AffinePoint<Self::TwistedCurve>) -> AffinePoint<Self> {
+        match q {
+            AffinePoint::XY(x, y) => {
+                let w2 = Self::omega() * Self::omega();
+                let w3 = w2.clone() * Self::omega();
+                AffinePoint::new_unchecked(Self::embed(x) * w2, Self::embed(y) * w3)
+            }
+            AffinePoint::Infinity => AffinePoint::Infinity,
+        }
+    }
+
+    /// The inverse of `untwist`. Fails with `NotInSubgroup` for the points
+    /// that are not images of the twist, such as non-trivial G1 points.
+    fn twist(q: &AffinePoint<Self>) -> Result<AffinePoint<Self::TwistedCurve>, CurveError> {
+        match q {
+            AffinePoint::XY(x, y) => {
+                let w2 = Self::omega() * Self::omega();
+                let w3 = w2.clone() * Self::omega();
+                let x = Self::restrict(&(x.clone() / w2)).ok_or(CurveError::NotInSubgroup)?;
+                let y = Self::restrict(&(y.clone() / w3)).ok_or(CurveError::NotInSubgroup)?;
+                AffinePoint::try_new(x, y)
+            }
+            AffinePoint::Infinity => Ok(AffinePoint::Infinity),
+        }
+    }
+
+    /// Checks that the twisted curve is y^2 = x^3 + a/ω^4 * x + b/ω^6,
+    /// that ω^d lies in the subfield and that the degrees match.
+    fn is_valid_twist() -> bool {
+        let d = Self::twist_degree();
+        let w2 = Self::omega() * Self::omega();
+        let w4 = w2.clone() * w2.clone();
+        let w6 = w4.clone() * w2;
+        let wd = (0..d).fold(FieldElement::one(), |acc, _| acc * Self::omega());
+
+        TwistField::<Self>::degree() * d == Self::BaseField::degree()
+            && Self::restrict(&wd).is_some()
+            && Self::embed(&Self::TwistedCurve::a()) == Self::a() / w4
+            && Self::embed(&Self::TwistedCurve::b()) == Self::b() / w6
+    }
+
+    /// A point of the twist untwists into G2 exactly when its order is r.
+    fn is_valid_twisted_g2(q: &AffinePoint<Self::TwistedCurve>) -> bool {
+        q.mul_double_and_add(&Self::ScalarField::to_bigint(&Self::r()))
+            .is_inf()
+    }
+
+    /// The endomorphism ψ = untwist^-1 ∘ π ∘ untwist of the twist. With
+    /// ξ = ω^d, it is ψ(x, y) = (x^q * ξ^(2(q-1)/d), y^q * ξ^(3(q-1)/d)), so
+    /// it only needs arithmetic over F_{q^(k/d)}.
+    fn psi(q: &AffinePoint<Self::TwistedCurve>) -> AffinePoint<Self::TwistedCurve> {
+        match q {
+            AffinePoint::XY(x, y) => {
+                let d = Self::twist_degree();
+                let (size, _) = point_counting::field_of_definition::<Self>();
+                let wd = (0..d).fold(FieldElement::one(), |acc, _| acc * Self::omega());
+                let xi = Self::restrict(&wd).expect("ω^d lies in the subfield");
+
+                let exponent = |n: u32| -> BigInt {
+                    let (exponent, rest) = ((&size - 1u32) * n).div_rem(&BigInt::from(d));
+                    debug_assert!(rest.is_zero(), "d does not divide {}(q - 1)", n);
+                    exponent
+                };
+                AffinePoint::new_unchecked(
+                    x.pow_bigint(&size) * xi.pow_bigint(&exponent(2)),
+                    y.pow_bigint(&size) * xi.pow_bigint(&exponent(3)),
+                )
+            }
+            AffinePoint::Infinity => AffinePoint::Infinity,
+        }
+    }
+
+    /// An alternative to `is_valid_twisted_g2`: ψ acts on G2 as
+    /// multiplication by λ = q mod r, which is about half as long as r on BN
+    /// curves, where λ = t - 1. A point with ψ(Q) = [λ]Q has an order
+    /// dividing λ^2 - tλ + q, t being the trace of Frobenius, and for BN
+    /// curves this is r itself, so the check is complete there.
+    fn is_valid_twisted_g2_psi(q: &AffinePoint<Self::TwistedCurve>) -> bool {
+        let (size, _) = point_counting::field_of_definition::<Self>();
+        let r = Self::ScalarField::to_bigint(&Self::r());
+        Self::psi(q) == q.mul_double_and_add(&(size % r))
+    }
+
+    /// The Tate pairing of a G1 point and a G2 point given on the twist.
+    fn tate_pairing_twisted(
+        p: &AffinePoint<Self>,
+        q: &AffinePoint<Self::TwistedCurve>,
+    ) -> FieldElement<Self::BaseField> {
+        assert!(Self::is_valid_g1(p), "p is not a G1 point");
+        assert!(Self::is_valid_twisted_g2(q), "q is not a G2 point");
+
+        let f = Self::miller_loop(p, &Self::untwist(q));
+        Self::final_exponentiation(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{MoonMath, MoonMathTwist, TinyJJ, TinyJJTwist},
+        elliptic_curve::points,
+        fields::{Fe13_2, Fe13_4, Fe43},
+        point_counting,
+        polynomial::Polynomial,
+    };
+
+    use super::*;
+
+    fn tinyjj_g2() -> AffinePoint<TinyJJ> {
+        AffinePoint::try_new_g2(
+            Polynomial::from(vec![7, 0, 4]).into(),
+            Polynomial::from(vec![0, 10, 0, 5]).into(),
+        )
+        .unwrap()
+    }
+
+    fn moonmath_g2() -> AffinePoint<MoonMath> {
+        AffinePoint::try_new_g2(
+            Polynomial::from(vec![0, 0, 10]).into(),
+            Polynomial::from(vec![0, 0, 0, 28]).into(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_twist_is_valid() {
+        assert!(TinyJJ::is_valid_twist());
+        assert!(MoonMath::is_valid_twist());
+    }
+
+    #[test]
+    fn test_twist_orders() {
+        assert_eq!(
+            point_counting::curve_order::<TinyJJTwist>(),
+            BigInt::from(TinyJJTwist::order())
+        );
+        assert_eq!(
+            point_counting::curve_order::<MoonMathTwist>(),
+            BigInt::from(MoonMathTwist::order())
+        );
+    }
+
+    #[test]
+    fn test_twist_embed_restrict() {
+        // t is embedded as x^2
+        let t: Fe13_2 = Polynomial::from(vec![0, 1]).into();
+        let x2: Fe13_4 = Polynomial::from(vec![0, 0, 1]).into();
+        assert_eq!(TinyJJ::embed(&t), x2);
+        assert_eq!(TinyJJ::restrict(&x2), Some(t));
+
+        let x: Fe13_4 = Polynomial::from(vec![0, 1]).into();
+        assert_eq!(TinyJJ::restrict(&x), None);
+
+        let c = Fe43::new(17);
+        assert_eq!(MoonMath::restrict(&MoonMath::embed(&c)), Some(c));
+    }
+
+    #[test]
+    fn test_twist_untwist() {
+        assert_eq!(TinyJJ::untwist(&TinyJJTwist::generator()), tinyjj_g2());
+        assert_eq!(TinyJJ::twist(&tinyjj_g2()), Ok(TinyJJTwist::generator()));
+
+        // MoonMath's generator is the untwisted (7, 16)
+        assert_eq!(
+            MoonMath::untwist(&MoonMathTwist::generator()),
+            MoonMath::generator()
+        );
+        let q = moonmath_g2();
+        assert_eq!(MoonMath::untwist(&MoonMath::twist(&q).unwrap()), q);
+
+        let p = AffinePoint::<TinyJJ>::try_new_g1(
+            Polynomial::from(vec![8]).into(),
+            Polynomial::from(vec![8]).into(),
+        )
+        .unwrap();
+        assert_eq!(TinyJJ::twist(&p), Err(CurveError::NotInSubgroup));
+        assert_eq!(
+            TinyJJ::twist(&AffinePoint::Infinity),
+            Ok(AffinePoint::Infinity)
+        );
+    }
+
+    #[test]
+    fn test_twist_group_law() {
+        // ψ is a group homomorphism
+        let q = TinyJJTwist::generator();
+        assert_eq!(
+            TinyJJ::untwist(&(q.clone() + q.double())),
+            TinyJJ::untwist(&q) + TinyJJ::untwist(&q.double())
+        );
+        let q = MoonMathTwist::generator();
+        assert_eq!(
+            TinyJJ::untwist(&AffinePoint::Infinity),
+            AffinePoint::Infinity
+        );
+        assert_eq!(
+            MoonMath::untwist(&(q.clone() * 3)),
+            MoonMath::generator() * 3
+        );
+
+        assert!(TinyJJ::is_valid_twisted_g2(&TinyJJTwist::generator()));
+        assert!(MoonMath::is_valid_twisted_g2(&MoonMathTwist::generator()));
+        assert!(MoonMath::is_valid_g2(&MoonMath::untwist(
+            &MoonMathTwist::generator()
+        )));
+    }
+
+    #[test]
+    fn test_twist_psi() {
+        // ψ is π seen through the twist
+        for q in [TinyJJTwist::generator(), TinyJJTwist::generator() * 7] {
+            assert_eq!(
+                TinyJJ::untwist(&TinyJJ::psi(&q)),
+                TinyJJ::untwist(&q).frobenius(1)
+            );
+        }
+        let q = MoonMathTwist::generator();
+        assert_eq!(
+            MoonMath::untwist(&MoonMath::psi(&q)),
+            MoonMath::generator().frobenius(1)
+        );
+
+        assert!(TinyJJ::is_valid_twisted_g2_psi(&TinyJJTwist::generator()));
+        assert!(MoonMath::is_valid_twisted_g2_psi(&q));
+        // a point passing the check has an order dividing 40, so not 3
+        let q = points::<TinyJJTwist>()
+            .find(|q| q.point_order() == BigInt::from(3))
+            .unwrap();
+        assert!(!TinyJJ::is_valid_twisted_g2_psi(&q));
+    }
+
+    #[test]
+    fn test_twist_pairing() {
+        let p = AffinePoint::<TinyJJ>::try_new_g1(
+            Polynomial::from(vec![8]).into(),
+            Polynomial::from(vec![8]).into(),
+        )
+        .unwrap();
+        let q = TinyJJTwist::generator();
+        assert_eq!(
+            TinyJJ::tate_pairing_twisted(&p, &q),
+            TinyJJ::tate_pairing(&p, &tinyjj_g2())
+        );
+
+        let p = AffinePoint::<MoonMath>::try_new_g1(
+            Polynomial::from(vec![27]).into(),
+            Polynomial::from(vec![34]).into(),
+        )
+        .unwrap();
+        let q = MoonMath::twist(&moonmath_g2()).unwrap();
+        let result: FieldElement<_> = Polynomial::from(vec![9, 4, 27, 12, 40, 21]).into();
+        assert_eq!(MoonMath::tate_pairing_twisted(&p, &q), result);
+    }
+}
@@ -0,0 +1,366 @@
+use std::fmt::{self, Display};
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Pow, ToPrimitive, Zero};
+
+use crate::{
+    elliptic_curve::{AffinePoint, EllipticCurve},
+    finite_field::{FiniteField, NonExtendedField},
+    number_theory::{is_prime, multiplicative_order},
+    point_counting::field_of_definition,
+};
+
+/// A check on the parameters of a curve that failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The characteristic of the base field is not prime.
+    CharacteristicNotPrime,
+    /// The modulus of the base field is not irreducible over its subfield.
+    ModulusNotIrreducible,
+    /// The modulus of the scalar field is not prime.
+    ScalarFieldNotPrime,
+    /// The discriminant -16(4a^3 + 27b^2) is zero.
+    Singular,
+    /// The generator is the point at infinity.
+    GeneratorAtInfinity,
+    /// The generator does not satisfy the curve equation.
+    GeneratorNotOnCurve,
+    /// Multiplying the generator by the order does not give the point at infinity.
+    GeneratorOrderMismatch,
+    /// The order is outside the Hasse interval [q + 1 - 2√q, q + 1 + 2√q].
+    OrderOutsideHasseBound,
+    /// r is not a prime number.
+    RNotPrime,
+    /// r does not divide the order.
+    RDoesNotDivideOrder,
+    /// The embedding degree is not the multiplicative order of q modulo r.
+    EmbeddingDegreeMismatch { expected: usize, found: usize },
+    /// The base field contains the embedding field, so the whole r-torsion
+    /// is defined over it and r^2 must divide the order, but it does not.
+    CofactorMismatch,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::CharacteristicNotPrime => {
+                write!(f, "the characteristic of the base field is not prime")
+            }
+            ValidationError::ModulusNotIrreducible => {
+                write!(f, "the modulus of the base field is not irreducible")
+            }
+            ValidationError::ScalarFieldNotPrime => {
+                write!(f, "the modulus of the scalar field is not prime")
+            }
+            ValidationError::Singular => write!(f, "the curve is singular"),
+            ValidationError::GeneratorAtInfinity => {
+                write!(f, "the generator is the point at infinity")
+            }
+            ValidationError::GeneratorNotOnCurve => write!(f, "the generator is not on the curve"),
+            ValidationError::GeneratorOrderMismatch => {
+                write!(f, "the order of the generator does not divide the order")
+            }
+            ValidationError::OrderOutsideHasseBound => {
+                write!(f, "the order is outside the Hasse bound")
+            }
+            ValidationError::RNotPrime => write!(f, "r is not prime"),
+            ValidationError::RDoesNotDivideOrder => write!(f, "r does not divide the order"),
+            ValidationError::EmbeddingDegreeMismatch { expected, found } => write!(
+                f,
+                "the embedding degree is {} but {} was given",
+                expected, found
+            ),
+            ValidationError::CofactorMismatch => {
+                write!(
+                    f,
+                    "r^2 does not divide the order despite the full r-torsion"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Runs every check on the parameters of the curve and returns all
+/// those that failed. Checks that depend on a failed one are skipped.
+///
+/// The order itself is only checked against the Hasse bound, see
+/// `point_counting::curve_order` to verify it exactly.
+pub fn validate<E: EllipticCurve>() -> Result<(), Vec<ValidationError>> {
+    let mut errors = vec![];
+
+    if !is_prime(&E::BaseField::characteristic()) {
+        errors.push(ValidationError::CharacteristicNotPrime);
+    } else if !E::BaseField::is_valid_modulus() {
+        errors.push(ValidationError::ModulusNotIrreducible);
+    }
+    if !E::ScalarField::is_prime_modulus() {
+        errors.push(ValidationError::ScalarFieldNotPrime);
+    }
+    // no element can be built outside a field
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    if E::discriminant().is_zero() {
+        errors.push(ValidationError::Singular);
+    }
+
+    let order = E::ScalarField::to_bigint(&E::order());
+    match E::generator() {
+        AffinePoint::Infinity => errors.push(ValidationError::GeneratorAtInfinity),
+        AffinePoint::XY(x, y) => {
+            if !AffinePoint::<E>::is_on_curve(&x, &y) {
+                errors.push(ValidationError::GeneratorNotOnCurve);
+            } else if !(E::generator() * E::order()).is_inf() {
+                errors.push(ValidationError::GeneratorOrderMismatch);
+            }
+        }
+    }
+
+    // (q + 1 - #E)^2 <= 4q
+    let q = E::BaseField::size();
+    let trace: BigInt = &q + 1u32 - &order;
+    if &trace * &trace > &q * 4u32 {
+        errors.push(ValidationError::OrderOutsideHasseBound);
+    }
+
+    let r = E::ScalarField::to_bigint(&E::r());
+    if !is_prime(&r) {
+        errors.push(ValidationError::RNotPrime);
+        return Err(errors);
+    }
+    if !order.is_multiple_of(&r) {
+        errors.push(ValidationError::RDoesNotDivideOrder);
+        return Err(errors);
+    }
+
+    let (q0, m) = field_of_definition::<E>();
+    let k = E::embedding_degree();
+    match multiplicative_order(&q0, &r).and_then(|k| k.to_usize()) {
+        Some(expected) if expected != k => {
+            errors.push(ValidationError::EmbeddingDegreeMismatch { expected, found: k });
+        }
+        // r divides q, so there is no embedding degree
+        None => errors.push(ValidationError::EmbeddingDegreeMismatch {
+            expected: 0,
+            found: k,
+        }),
+        _ => {
+            // the base field is F_{q0^m}, which contains F_{q0^k}
+            // exactly when k divides m
+            if m % k == 0 && !order.is_multiple_of(&Pow::pow(&r, 2u32)) {
+                errors.push(ValidationError::CofactorMismatch);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use derive_lib::polynomial_inverse;
+
+    use crate::{
+        curves::{MoonMath, TinyJJ},
+        field_element::FieldElement,
+        fields::{Fe13, Fe13_4, Ff13, Ff13_4},
+        polynomial::Polynomial,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_validate_shipped_curves() {
+        assert_eq!(TinyJJ::validate(), Ok(()));
+        assert_eq!(MoonMath::validate(), Ok(()));
+    }
+
+    /// TinyJJ with every parameter but the field broken.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Broken;
+
+    impl EllipticCurve for Broken {
+        type BaseField = Ff13_4;
+        type ScalarField = Ff13;
+
+        fn a() -> Fe13_4 {
+            Polynomial::from(vec![0]).into()
+        }
+
+        fn b() -> Fe13_4 {
+            Polynomial::from(vec![0]).into()
+        }
+
+        fn embedding_degree() -> usize {
+            2
+        }
+
+        fn generator() -> AffinePoint<Self> {
+            AffinePoint::new_unchecked(
+                Polynomial::from(vec![8]).into(),
+                Polynomial::from(vec![8]).into(),
+            )
+        }
+
+        fn order() -> i16 {
+            28800
+        }
+
+        fn r() -> i16 {
+            5
+        }
+    }
+
+    #[test]
+    fn test_validate_broken_curve() {
+        assert_eq!(
+            Broken::validate(),
+            Err(vec![
+                ValidationError::Singular,
+                ValidationError::GeneratorNotOnCurve,
+                ValidationError::EmbeddingDegreeMismatch {
+                    expected: 4,
+                    found: 2
+                },
+            ])
+        );
+    }
+
+    /// TinyJJ with a wrong order and r.
+    #[derive(Debug, Clone, PartialEq)]
+    struct WrongOrder;
+
+    impl EllipticCurve for WrongOrder {
+        type BaseField = Ff13_4;
+        type ScalarField = Ff13;
+
+        fn a() -> Fe13_4 {
+            TinyJJ::a()
+        }
+
+        fn b() -> Fe13_4 {
+            TinyJJ::b()
+        }
+
+        fn embedding_degree() -> usize {
+            4
+        }
+
+        fn generator() -> AffinePoint<Self> {
+            // try_new would refuse the curve, since r is not prime
+            let (x, y) = TinyJJ::generator().xy().unwrap();
+            AffinePoint::new_unchecked(x, y)
+        }
+
+        fn order() -> i16 {
+            30000
+        }
+
+        fn r() -> i16 {
+            15
+        }
+    }
+
+    #[test]
+    fn test_validate_wrong_order() {
+        assert_eq!(
+            WrongOrder::validate(),
+            Err(vec![
+                ValidationError::GeneratorOrderMismatch,
+                ValidationError::OrderOutsideHasseBound,
+                ValidationError::RNotPrime,
+            ])
+        );
+    }
+
+    /// F_13[x] / (x^4 + 1), which is not a field: x^4 + 1 is the product of
+    /// x^2 + 5 and x^2 + 8 over F_13.
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    struct Ff13Split;
+
+    impl FiniteField for Ff13Split {
+        type T = Polynomial<Fe13>;
+
+        fn modulus() -> Self::T {
+            Polynomial::from(vec![1, 0, 0, 0, 1])
+        }
+
+        fn zero() -> Self::T {
+            Polynomial::new(vec![Fe13::zero()])
+        }
+
+        fn one() -> Self::T {
+            Polynomial::new(vec![Fe13::one()])
+        }
+
+        #[polynomial_inverse]
+        fn inverse(value: &Self::T) -> Self::T;
+    }
+
+    /// TinyJJ over a ring that is not a field.
+    #[derive(Debug, Clone, PartialEq)]
+    struct NotAField;
+
+    impl EllipticCurve for NotAField {
+        type BaseField = Ff13Split;
+        type ScalarField = Ff13;
+
+        fn a() -> FieldElement<Ff13Split> {
+            Polynomial::from(vec![8]).into()
+        }
+
+        fn b() -> FieldElement<Ff13Split> {
+            Polynomial::from(vec![8]).into()
+        }
+
+        fn embedding_degree() -> usize {
+            4
+        }
+
+        fn generator() -> AffinePoint<Self> {
+            AffinePoint::new_unchecked(
+                Polynomial::from(vec![8]).into(),
+                Polynomial::from(vec![8]).into(),
+            )
+        }
+
+        fn order() -> i16 {
+            28800
+        }
+
+        fn r() -> i16 {
+            5
+        }
+    }
+
+    #[test]
+    fn test_validate_reducible_modulus() {
+        assert!(Ff13_4::is_valid_modulus());
+        assert!(!Ff13Split::is_valid_modulus());
+        assert_eq!(
+            NotAField::validate(),
+            Err(vec![ValidationError::ModulusNotIrreducible])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not define a field")]
+    fn test_reducible_modulus_element() {
+        FieldElement::<Ff13Split>::new(Polynomial::from(vec![1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "r is not a prime divisor of the order")]
+    fn test_wrong_order_point() {
+        let (x, y) = TinyJJ::generator().xy().unwrap();
+        let _ = AffinePoint::<WrongOrder>::try_new(x, y);
+    }
+}
@@ -0,0 +1,498 @@
+use std::ops::{Add, Neg};
+
+use num_bigint::BigInt;
+use num_traits::{Signed, Zero};
+
+use crate::{
+    elliptic_curve::{AffinePoint, CurveError, EllipticCurve},
+    field_element::FieldElement,
+    finite_field::FiniteField,
+};
+
+// Long Weierstrass equations.
+//
+// The general Weierstrass equation
+//
+//     y^2 + a1*x*y + a3*y = x^3 + a2*x^2 + a4*x + a6
+//
+// describes every elliptic curve, including those over fields of
+// characteristic 2 and 3, where the short form y^2 = x^3 + ax + b does not
+// exist. With the usual quantities
+//
+//     b2 = a1^2 + 4a2,  b4 = 2a4 + a1a3,  b6 = a3^2 + 4a6,
+//     b8 = a1^2a6 + 4a2a6 - a1a3a4 + a2a3^2 - a4^2,
+//     c4 = b2^2 - 24b4,  c6 = -b2^3 + 36b2b4 - 216b6,
+//
+// the discriminant is Δ = -b2^2b8 - 8b4^3 - 27b6^2 + 9b2b4b6 and the
+// j-invariant is c4^3 / Δ. When the characteristic is neither 2 nor 3, the
+// change of variables (x, y) -> (36x + 3b2, 108(2y + a1x + a3)) maps the curve
+// to the short form y^2 = x^3 - 27c4 * x - 54c6.
+
+fn constant<M: FiniteField>(n: i64) -> FieldElement<M> {
+    FieldElement::new(M::from_coefficients(&[BigInt::from(n)]))
+}
+
+pub trait WeierstrassCurve: Clone + PartialEq {
+    type BaseField: FiniteField;
+
+    fn a1() -> FieldElement<Self::BaseField>;
+    fn a2() -> FieldElement<Self::BaseField>;
+    fn a3() -> FieldElement<Self::BaseField>;
+    fn a4() -> FieldElement<Self::BaseField>;
+    fn a6() -> FieldElement<Self::BaseField>;
+
+    /// The quantities b2, b4, b6 and b8.
+    fn b_invariants() -> [FieldElement<Self::BaseField>; 4] {
+        let (a1, a2, a3, a4, a6) = (Self::a1(), Self::a2(), Self::a3(), Self::a4(), Self::a6());
+        let four = constant(4);
+
+        let b2 = a1.clone() * a1.clone() + four.clone() * a2.clone();
+        let b4 = constant::<Self::BaseField>(2) * a4.clone() + a1.clone() * a3.clone();
+        let b6 = a3.clone() * a3.clone() + four.clone() * a6.clone();
+        let b8 = a1.clone() * a1.clone() * a6.clone() + four * a2.clone() * a6
+            - a1 * a3.clone() * a4.clone()
+            + a2 * a3.clone() * a3
+            - a4.clone() * a4;
+        [b2, b4, b6, b8]
+    }
+
+    /// The quantities c4 and c6.
+    fn c_invariants() -> [FieldElement<Self::BaseField>; 2] {
+        let [b2, b4, b6, _] = Self::b_invariants();
+        let c4 = b2.clone() * b2.clone() - constant(24) * b4.clone();
+        let c6 =
+            -(b2.clone() * b2.clone() * b2.clone()) + constant(36) * b2 * b4 - constant(216) * b6;
+        [c4, c6]
+    }
+
+    fn discriminant() -> FieldElement<Self::BaseField> {
+        let [b2, b4, b6, b8] = Self::b_invariants();
+        -(b2.clone() * b2.clone() * b8)
+            - constant(8) * b4.clone() * b4.clone() * b4.clone()
+            - constant(27) * b6.clone() * b6.clone()
+            + constant(9) * b2 * b4 * b6
+    }
+
+    fn is_singular() -> bool {
+        Self::discriminant().is_zero()
+    }
+
+    /// The j-invariant c4^3 / Δ, or None for a singular curve.
+    fn j_invariant() -> Option<FieldElement<Self::BaseField>> {
+        if Self::is_singular() {
+            return None;
+        }
+        let [c4, _] = Self::c_invariants();
+        Some(c4.clone() * c4.clone() * c4 / Self::discriminant())
+    }
+}
+
+/// A long Weierstrass curve over a field of characteristic other than
+/// 2 and 3, together with its short form.
+pub trait ShortWeierstrassForm: WeierstrassCurve {
+    type Short: EllipticCurve<BaseField = Self::BaseField>;
+
+    /// Checks the characteristic and that `Short` is y^2 = x^3 - 27c4 * x - 54c6.
+    fn is_valid_model() -> bool {
+        let p = Self::BaseField::characteristic();
+        let [c4, c6] = Self::c_invariants();
+        p != BigInt::from(2)
+            && p != BigInt::from(3)
+            && Self::Short::a() == constant::<Self::BaseField>(-27) * c4
+            && Self::Short::b() == constant::<Self::BaseField>(-54) * c6
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum WeierstrassPoint<C: WeierstrassCurve> {
+    Infinity,
+    XY(FieldElement<C::BaseField>, FieldElement<C::BaseField>),
+}
+
+impl<C: WeierstrassCurve> WeierstrassPoint<C> {
+    pub fn try_new(
+        x: FieldElement<C::BaseField>,
+        y: FieldElement<C::BaseField>,
+    ) -> Result<Self, CurveError> {
+        if Self::is_on_curve(&x, &y) {
+            Ok(WeierstrassPoint::XY(x, y))
+        } else {
+            Err(CurveError::NotOnCurve)
+        }
+    }
+
+    pub fn is_on_curve(x: &FieldElement<C::BaseField>, y: &FieldElement<C::BaseField>) -> bool {
+        let x2 = x.clone() * x.clone();
+        y.clone() * y.clone() + C::a1() * x.clone() * y.clone() + C::a3() * y.clone()
+            == x2.clone() * x.clone() + C::a2() * x2 + C::a4() * x.clone() + C::a6()
+    }
+
+    pub fn is_inf(&self) -> bool {
+        matches!(self, WeierstrassPoint::Infinity)
+    }
+
+    /// Adds two points through the slope λ and intercept ν of the line
+    /// through them, which is the tangent when they are equal.
+    fn add_points(&self, other: &Self) -> Self {
+        let (x1, y1, x2, y2) = match (self, other) {
+            (WeierstrassPoint::Infinity, p) | (p, WeierstrassPoint::Infinity) => return p.clone(),
+            (WeierstrassPoint::XY(x1, y1), WeierstrassPoint::XY(x2, y2)) => {
+                (x1.clone(), y1.clone(), x2.clone(), y2.clone())
+            }
+        };
+
+        let (m, v) = if x1 != x2 {
+            let dx = x2.clone() - x1.clone();
+            let m = (y2.clone() - y1.clone()) / dx.clone();
+            let v = (y1.clone() * x2.clone() - y2 * x1.clone()) / dx;
+            (m, v)
+        } else {
+            // a vertical line through P and -P or through a point of order two
+            let den = constant::<C::BaseField>(2) * y1.clone() + C::a1() * x1.clone() + C::a3();
+            if y1 != y2 || den.is_zero() {
+                return WeierstrassPoint::Infinity;
+            }
+            let x1_2 = x1.clone() * x1.clone();
+            let m = (constant::<C::BaseField>(3) * x1_2.clone()
+                + constant::<C::BaseField>(2) * C::a2() * x1.clone()
+                + C::a4()
+                - C::a1() * y1.clone())
+                / den.clone();
+            let v = (-(x1_2 * x1.clone())
+                + C::a4() * x1.clone()
+                + constant::<C::BaseField>(2) * C::a6()
+                - C::a3() * y1)
+                / den;
+            (m, v)
+        };
+
+        let x3 = m.clone() * m.clone() + C::a1() * m.clone() - C::a2() - x1 - x2;
+        let y3 = -((m + C::a1()) * x3.clone()) - v - C::a3();
+        WeierstrassPoint::XY(x3, y3)
+    }
+
+    pub fn double(&self) -> Self {
+        self.add_points(self)
+    }
+
+    /// Double-and-add over the bits of |k|; a negative k multiplies the negated point.
+    pub fn mul_double_and_add(&self, k: &BigInt) -> Self {
+        let magnitude = k.magnitude();
+        let mut point = WeierstrassPoint::Infinity;
+        for i in (0..magnitude.bits()).rev() {
+            point = point.double();
+            if magnitude.bit(i) {
+                point = point + self.clone();
+            }
+        }
+
+        if k.is_negative() {
+            -point
+        } else {
+            point
+        }
+    }
+}
+
+impl<C: ShortWeierstrassForm> WeierstrassPoint<C> {
+    /// Maps the point to the short form with (x, y) -> (36x + 3b2, 108(2y + a1x + a3)).
+    pub fn to_short(&self) -> AffinePoint<C::Short> {
+        match self {
+            WeierstrassPoint::XY(x, y) => {
+                let [b2, ..] = C::b_invariants();
+                let new_x = constant::<C::BaseField>(36) * x.clone() + constant(3) * b2;
+                let new_y = constant::<C::BaseField>(108)
+                    * (constant::<C::BaseField>(2) * y.clone() + C::a1() * x.clone() + C::a3());
+                AffinePoint::try_new(new_x, new_y).expect("invalid short Weierstrass model")
+            }
+            WeierstrassPoint::Infinity => AffinePoint::Infinity,
+        }
+    }
+
+    /// The inverse of `to_short`.
+    pub fn from_short(p: &AffinePoint<C::Short>) -> Self {
+        match p {
+            AffinePoint::XY(x, y) => {
+                let [b2, ..] = C::b_invariants();
+                let new_x = (x.clone() - constant(3) * b2) / constant(36);
+                let new_y =
+                    (y.clone() / constant(108) - C::a1() * new_x.clone() - C::a3()) / constant(2);
+                WeierstrassPoint::XY(new_x, new_y)
+            }
+            AffinePoint::Infinity => WeierstrassPoint::Infinity,
+        }
+    }
+}
+
+impl<C: WeierstrassCurve> Add for WeierstrassPoint<C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.add_points(&other)
+    }
+}
+
+/// The negation -(x, y) = (x, -y - a1*x - a3).
+impl<C: WeierstrassCurve> Neg for WeierstrassPoint<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self {
+            WeierstrassPoint::XY(x, y) => {
+                let new_y = -y - C::a1() * x.clone() - C::a3();
+                WeierstrassPoint::XY(x, new_y)
+            }
+            WeierstrassPoint::Infinity => WeierstrassPoint::Infinity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use derive_lib::polynomial_inverse;
+
+    use crate::{
+        curves::{MoonMath, TinyJJ},
+        fields::{Fe43, Ff43},
+        polynomial::Polynomial,
+    };
+
+    use super::*;
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    struct Ff2;
+
+    impl FiniteField for Ff2 {
+        type T = i16;
+
+        fn modulus() -> i16 {
+            2
+        }
+
+        fn one() -> i16 {
+            1
+        }
+
+        fn zero() -> i16 {
+            0
+        }
+    }
+
+    /// F_16 = F_2[x] / (x^4 + x + 1)
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    struct Ff2_4;
+
+    impl FiniteField for Ff2_4 {
+        type T = Polynomial<FieldElement<Ff2>>;
+
+        fn modulus() -> Self::T {
+            Polynomial::from(vec![1, 1, 0, 0, 1])
+        }
+
+        fn zero() -> Self::T {
+            Polynomial::new(vec![FieldElement::zero()])
+        }
+
+        fn one() -> Self::T {
+            Polynomial::new(vec![FieldElement::one()])
+        }
+
+        #[polynomial_inverse]
+        fn inverse(value: &Self::T) -> Self::T;
+    }
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    struct Ff3;
+
+    impl FiniteField for Ff3 {
+        type T = i16;
+
+        fn modulus() -> i16 {
+            3
+        }
+
+        fn one() -> i16 {
+            1
+        }
+
+        fn zero() -> i16 {
+            0
+        }
+    }
+
+    /// y^2 + xy = x^3 + x^2 + 1 over F_16
+    #[derive(Debug, Clone, PartialEq)]
+    struct Char2;
+
+    impl WeierstrassCurve for Char2 {
+        type BaseField = Ff2_4;
+
+        fn a1() -> FieldElement<Ff2_4> {
+            FieldElement::one()
+        }
+
+        fn a2() -> FieldElement<Ff2_4> {
+            FieldElement::one()
+        }
+
+        fn a3() -> FieldElement<Ff2_4> {
+            FieldElement::zero()
+        }
+
+        fn a4() -> FieldElement<Ff2_4> {
+            FieldElement::zero()
+        }
+
+        fn a6() -> FieldElement<Ff2_4> {
+            FieldElement::one()
+        }
+    }
+
+    /// y^2 = x^3 + x^2 + 1 over F_3
+    #[derive(Debug, Clone, PartialEq)]
+    struct Char3;
+
+    impl WeierstrassCurve for Char3 {
+        type BaseField = Ff3;
+
+        fn a1() -> FieldElement<Ff3> {
+            FieldElement::zero()
+        }
+
+        fn a2() -> FieldElement<Ff3> {
+            FieldElement::one()
+        }
+
+        fn a3() -> FieldElement<Ff3> {
+            FieldElement::zero()
+        }
+
+        fn a4() -> FieldElement<Ff3> {
+            FieldElement::zero()
+        }
+
+        fn a6() -> FieldElement<Ff3> {
+            FieldElement::one()
+        }
+    }
+
+    /// y^2 + y = x^3 - x over F_43, the reduction of the curve 37a1
+    #[derive(Debug, Clone, PartialEq)]
+    struct Curve37;
+
+    impl WeierstrassCurve for Curve37 {
+        type BaseField = Ff43;
+
+        fn a1() -> Fe43 {
+            Fe43::new(0)
+        }
+
+        fn a2() -> Fe43 {
+            Fe43::new(0)
+        }
+
+        fn a3() -> Fe43 {
+            Fe43::new(1)
+        }
+
+        fn a4() -> Fe43 {
+            Fe43::new(-1)
+        }
+
+        fn a6() -> Fe43 {
+            Fe43::new(0)
+        }
+    }
+
+    impl ShortWeierstrassForm for Curve37 {
+        type Short = Curve37Short;
+    }
+
+    /// y^2 = x^3 + 37x + 11 over F_43
+    #[derive(Debug, Clone, PartialEq)]
+    struct Curve37Short;
+
+    impl EllipticCurve for Curve37Short {
+        type BaseField = Ff43;
+        type ScalarField = Ff43;
+
+        fn a() -> Fe43 {
+            Fe43::new(37)
+        }
+
+        fn b() -> Fe43 {
+            Fe43::new(11)
+        }
+
+        fn generator() -> AffinePoint<Self> {
+            AffinePoint::try_new(Fe43::new(0), Fe43::new(22)).unwrap()
+        }
+    }
+
+    fn all_points<C: WeierstrassCurve>() -> Vec<WeierstrassPoint<C>> {
+        let elements: Vec<_> = FieldElement::<C::BaseField>::elements().collect();
+        let mut points = vec![WeierstrassPoint::Infinity];
+        for x in &elements {
+            for y in &elements {
+                if let Ok(p) = WeierstrassPoint::try_new(x.clone(), y.clone()) {
+                    points.push(p);
+                }
+            }
+        }
+        points
+    }
+
+    fn assert_group_law<C: WeierstrassCurve>() {
+        let points = all_points::<C>();
+        let n = BigInt::from(points.len());
+        for p in &points {
+            assert!((p.clone() + -p.clone()).is_inf());
+            assert!(p.mul_double_and_add(&n).is_inf());
+            if let WeierstrassPoint::XY(x, y) = p.double() {
+                assert!(WeierstrassPoint::<C>::is_on_curve(&x, &y));
+            }
+        }
+        for p in points.iter().take(4) {
+            for q in &points {
+                let r = points[points.len() - 1].clone();
+                assert!((p.clone() + q.clone()) + r.clone() == p.clone() + (q.clone() + r));
+            }
+        }
+    }
+
+    #[test]
+    fn test_weierstrass_discriminant() {
+        assert_eq!(Curve37::discriminant(), Fe43::new(37));
+        assert_eq!(
+            Curve37::j_invariant(),
+            Some(Fe43::new(110592) / Fe43::new(37))
+        );
+        assert_eq!(Char2::discriminant(), FieldElement::one());
+        assert_eq!(Char2::j_invariant(), Some(FieldElement::one()));
+        assert!(!Char3::is_singular());
+
+        assert_eq!(Curve37::j_invariant(), Curve37Short::j_invariant());
+        assert_eq!(MoonMath::j_invariant(), Some(FieldElement::zero()));
+        assert!(TinyJJ::j_invariant().is_some());
+    }
+
+    #[test]
+    fn test_weierstrass_group_law() {
+        assert_group_law::<Char2>();
+        assert_group_law::<Char3>();
+        assert_group_law::<Curve37>();
+    }
+
+    #[test]
+    fn test_weierstrass_short_form() {
+        assert!(Curve37::is_valid_model());
+
+        let p = WeierstrassPoint::<Curve37>::try_new(Fe43::new(0), Fe43::new(0)).unwrap();
+        assert_eq!(p.to_short(), Curve37Short::generator());
+        assert_eq!(WeierstrassPoint::from_short(&p.to_short()), p);
+
+        let q = p.double() + p.clone();
+        assert_eq!(q.to_short(), Curve37Short::generator() * 3);
+        assert_eq!((-p).to_short(), -Curve37Short::generator());
+    }
+}
@@ -0,0 +1,194 @@
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+use pairings_from_scratch::{
+    ate::AtePairing,
+    curves::{Bn254, Bn254Fp, Bn254Twist},
+    elliptic_curve::{AffinePoint, CurveError, EllipticCurve},
+    field_element::FieldElement,
+    fields::{FfBn254_12, FfBn254_2},
+    glv::GlvCurve,
+    hash_to_curve::{hash_to_field, HashToCurve, HashToG2},
+    pairing::Pairing,
+    polynomial::Polynomial,
+    subfield::SubfieldCurve,
+    twist::Twist,
+};
+
+#[test]
+fn test_bn254_twist() {
+    assert!(Bn254::is_valid_twist());
+
+    let q = Bn254Twist::generator();
+    assert!(Bn254::is_valid_twisted_g2(&q));
+
+    let (x, y) = Bn254::untwist(&q).xy().unwrap();
+    let untwisted = AffinePoint::<Bn254>::try_new(x, y).unwrap();
+    assert_eq!(Bn254::twist(&untwisted), Ok(q.clone()));
+    assert_eq!(Bn254::untwist(&q.double()), untwisted.double());
+
+    // G1 points are not images of the twist
+    assert_eq!(
+        Bn254::twist(&Bn254::generator()),
+        Err(CurveError::NotInSubgroup)
+    );
+}
+
+#[test]
+fn test_bn254_glv() {
+    let p = Bn254::generator();
+    assert_eq!(p.endomorphism(), p.mul_double_and_add(&Bn254::lambda()));
+
+    let r = Bn254::r();
+    let bound = BigInt::one() << 128;
+    for k in [
+        BigInt::from(0),
+        BigInt::from(1),
+        r.clone() - 1,
+        BigInt::from_str("1234567890123456789012345678901234567890123456789012345678901234567")
+            .unwrap(),
+    ] {
+        let (k1, k2) = Bn254::glv_decompose(&k);
+        assert_eq!(
+            (&k1 + &k2 * Bn254::lambda() - &k).mod_floor(&r),
+            BigInt::zero()
+        );
+        assert!(k1.abs() < bound && k2.abs() < bound);
+    }
+
+    let k = BigInt::from_str("98765432109876543210987654321098765432109876543210").unwrap();
+    assert_eq!(p.mul_glv(k.clone()), p.mul_double_and_add(&k));
+}
+
+#[test]
+fn test_bn254_hash_to_curve() {
+    // the BN254G1_XMD:SHA-256_SVDW_RO_ suite, with Z = 1
+    let dst = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+    let coordinate = |hex: &str| -> FieldElement<FfBn254_12> {
+        let c = BigInt::parse_bytes(hex.as_bytes(), 16).unwrap();
+        Polynomial::from(vec![c]).into()
+    };
+    let q128 = format!("q128_{}", "q".repeat(128));
+    let a512 = format!("a512_{}", "a".repeat(512));
+    for (msg, x, y) in [
+        (
+            "",
+            "0a976ab906170db1f9638d376514dbf8c42aef256a54bbd48521f20749e59e86",
+            "02925ead66b9e68bfc309b014398640ab55f6619ab59bc1fab2210ad4c4d53d5",
+        ),
+        (
+            "abc",
+            "23f717bee89b1003957139f193e6be7da1df5f1374b26a4643b0378b5baf53d1",
+            "04142f826b71ee574452dbc47e05bc3e1a647478403a7ba38b7b93948f4e151d",
+        ),
+        (
+            "abcdef0123456789",
+            "187dbf1c3c89aceceef254d6548d7163fdfa43084145f92c4c91c85c21442d4a",
+            "0abd99d5b0000910b56058f9cc3b0ab0a22d47cf27615f588924fac1e5c63b4d",
+        ),
+        (
+            &q128,
+            "00fe2b0743575324fc452d590d217390ad48e5a16cf051bee5c40a2eba233f5c",
+            "0794211e0cc72d3cbbdf8e4e5cd6e7d7e78d101ff94862caae8acbe63e9fdc78",
+        ),
+        (
+            &a512,
+            "01b05dc540bd79fd0fea4fbb07de08e94fc2e7bd171fe025c479dc212a2173ce",
+            "1bf028afc00c0f843d113758968f580640541728cfc6d32ced9779aa613cd9b0",
+        ),
+    ] {
+        assert_eq!(
+            Bn254::hash_to_g1(msg.as_bytes(), dst),
+            AffinePoint::try_new(coordinate(x), coordinate(y)).unwrap(),
+            "msg = {:?}",
+            msg
+        );
+    }
+
+    // the BN254G2_XMD:SHA-256_SVDW_RO_ suite, hashed on the twist
+    let dst = b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_RO_";
+    let coordinate = |c0: &str, c1: &str| -> FieldElement<FfBn254_2> {
+        let c = |hex: &str| BigInt::parse_bytes(hex.as_bytes(), 16).unwrap();
+        Polynomial::from(vec![c(c0), c(c1)]).into()
+    };
+    for (msg, x, y) in [
+        (
+            "",
+            (
+                "1192005a0f121921a6d5629946199e4b27ff8ee4d6dd4f9581dc550ade851300",
+                "1747d950a6f23c16156e2171bce95d1189b04148ad12628869ed21c96a8c9335",
+            ),
+            (
+                "0498f6bb5ac309a07d9a8b88e6ff4b8de0d5f27a075830e1eb0e68ea318201d8",
+                "2c9755350ca363ef2cf541005437221c5740086c2e909b71d075152484e845f4",
+            ),
+        ),
+        (
+            "abc",
+            (
+                "16c88b54eec9af86a41569608cd0f60aab43464e52ce7e6e298bf584b94fccd2",
+                "0b5db3ca7e8ef5edf3a33dfc3242357fbccead98099c3eb564b3d9d13cba4efd",
+            ),
+            (
+                "1c42ba524cb74db8e2c680449746c028f7bea923f245e69f89256af2d6c5f3ac",
+                "22d02d2da7f288545ff8789e789902245ab08c6b1d253561eec789ec2c1bd630",
+            ),
+        ),
+    ] {
+        let q = AffinePoint::try_new(coordinate(x.0, x.1), coordinate(y.0, y.1)).unwrap();
+        assert!(Bn254::is_valid_twisted_g2(&q));
+        assert_eq!(
+            Bn254Twist::hash_to_curve(msg.as_bytes(), dst),
+            q,
+            "msg = {:?}",
+            msg
+        );
+        assert_eq!(Bn254::hash_to_g2(msg.as_bytes(), dst), Bn254::untwist(&q));
+    }
+}
+
+#[test]
+fn test_bn254_subgroup_checks() {
+    assert!(Bn254::is_valid_g1_frobenius(&Bn254::generator()));
+
+    let q = Bn254Twist::generator();
+    assert!(Bn254::is_valid_twisted_g2_psi(&q));
+
+    // the twist has cofactor 2p - r, so a point before cofactor clearing
+    // is not in G2
+    let u = hash_to_field(b"abc", b"psi", 1, 2);
+    let q = Bn254Twist::map_to_curve(&u[0]);
+    assert!(!Bn254::is_valid_twisted_g2_psi(&q));
+}
+
+#[test]
+fn test_bn254_subfield() {
+    assert!(Bn254::is_valid_descent());
+
+    let p = Bn254::generator();
+    assert_eq!(p.try_descend(), Some(Bn254Fp::generator()));
+    assert_eq!(AffinePoint::<Bn254>::lift(&Bn254Fp::generator()), p);
+
+    let q = Bn254::untwist(&Bn254Twist::generator());
+    assert_eq!(q.try_descend(), None);
+}
+
+#[test]
+fn test_bn254_optimal_ate() {
+    // 6u + 2 has 65 bits where r has 254
+    assert_eq!(Bn254::optimal_ate_loop_count().bits(), 65);
+    assert_eq!(Bn254::trace(), BigInt::from(6) * Bn254::u().pow(2) + 1);
+
+    let p = Bn254::generator();
+    let q = Bn254Twist::generator();
+    let e = Bn254::optimal_ate_pairing(&p, &q);
+    assert_ne!(e, FieldElement::one());
+
+    // e(2P, Q) = e(P, 2Q), with a single final exponentiation since the
+    // pairings are slow in debug builds
+    let f = Bn254::optimal_ate_miller_loop(&p.double(), &q)
+        / Bn254::optimal_ate_miller_loop(&p, &q.mul_double_and_add(&BigInt::from(2)));
+    assert_eq!(Bn254::final_exponentiation(f), FieldElement::one());
+}